@@ -0,0 +1,45 @@
+//!
+//! Throughput of the scalar `base64` engine against the `simd` backend
+//! that `ancryptor` uses with the `simd` feature, on a 1 MB buffer.
+//!
+//! ## Examples
+//! ```
+//! $ cd ancryptor/
+//! $ cargo bench --features simd --bench base64_backends
+//! ```
+//!
+
+use base64::{
+    Engine as _,
+    engine::general_purpose::STANDARD as base64Engine
+};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+static BUFFER_SIZE: usize = 1024 * 1024;
+
+fn buffer() -> Vec<u8> {
+    (0..BUFFER_SIZE).map(|index| (index * 31 % 251) as u8).collect()
+}
+
+fn encode_benchmark(criterion: &mut Criterion) {
+    let data = buffer();
+    let mut group = criterion.benchmark_group("encode 1MB");
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+
+    group.bench_function("scalar", |bencher| bencher.iter(|| base64Engine.encode(black_box(&data))));
+    group.bench_function("simd", |bencher| bencher.iter(|| ancryptor::encode_bytes(black_box(&data))));
+    group.finish();
+}
+
+fn decode_benchmark(criterion: &mut Criterion) {
+    let encoded = base64Engine.encode(buffer());
+    let mut group = criterion.benchmark_group("decode 1MB");
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+
+    group.bench_function("scalar", |bencher| bencher.iter(|| base64Engine.decode(black_box(&encoded))));
+    group.bench_function("simd", |bencher| bencher.iter(|| ancryptor::decode_bytes(black_box(&encoded))));
+    group.finish();
+}
+
+criterion_group!(benches, encode_benchmark, decode_benchmark);
+criterion_main!(benches);
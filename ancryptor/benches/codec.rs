@@ -0,0 +1,80 @@
+//!
+//! Throughput of `encode`/`decode` and their url-safe variants across
+//! input sizes, as a baseline for PRs to show their impact against.
+//!
+//! ## Examples
+//! ```
+//! $ cd ancryptor/
+//! $ cargo bench --bench codec
+//! $ cargo bench --bench codec -- --save-baseline main
+//! $ cargo bench --bench codec -- --baseline main
+//! ```
+//!
+
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+
+// 64 B, 4 KB and 1 MB
+static INPUT_SIZES: [usize; 3] = [64, 4 * 1024, 1024 * 1024];
+
+// Any fixed seed, so every run benchmarks the same input.
+static SEED: u64 = 0x5EED;
+
+///
+/// Returns `len` pseudo-random printable ASCII characters, the same for
+/// every run: `encode` takes text, so the input has to be valid UTF-8.
+///
+fn pseudo_random_text(len: usize) -> String {
+    let mut state = SEED;
+
+    (0..len)
+        .map(|_| {
+            // xorshift64
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (b' ' + (state % 95) as u8) as char
+        })
+        .collect()
+}
+
+fn encode_benchmark(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("encode");
+
+    for size in INPUT_SIZES {
+        let text = pseudo_random_text(size);
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("standard", size), &text, |bencher, text| {
+            bencher.iter(|| ancryptor::encode(black_box(text)))
+        });
+        group.bench_with_input(BenchmarkId::new("url-safe", size), &text, |bencher, text| {
+            bencher.iter(|| ancryptor::encode_url_safe(black_box(text)))
+        });
+    }
+
+    group.finish();
+}
+
+fn decode_benchmark(criterion: &mut Criterion) {
+    let mut group = criterion.benchmark_group("decode");
+
+    for size in INPUT_SIZES {
+        let text = pseudo_random_text(size);
+        let encoded = ancryptor::encode(&text);
+        let encoded_url_safe = ancryptor::encode_url_safe(&text);
+        // Throughput is measured in decoded bytes, like for `encode`.
+        group.throughput(Throughput::Bytes(size as u64));
+
+        group.bench_with_input(BenchmarkId::new("standard", size), &encoded, |bencher, encoded| {
+            bencher.iter(|| ancryptor::decode(black_box(encoded)))
+        });
+        group.bench_with_input(BenchmarkId::new("url-safe", size), &encoded_url_safe, |bencher, encoded| {
+            bencher.iter(|| ancryptor::decode_url_safe(black_box(encoded)))
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, encode_benchmark, decode_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,33 @@
+///
+/// Compares sequential and parallel batch decoding on 100k short strings.
+///
+/// ## Examples
+/// ```
+/// $ cd ancryptor/
+/// $ cargo run --release --example batch_decode --features rayon
+/// ```
+///
+use std::time::Instant;
+
+static BATCH_SIZE: usize = 100_000;
+
+fn main() {
+    let inputs: Vec<String> = (0..BATCH_SIZE)
+        .map(|index| ancryptor::encode(&format!("record-{:08}", index)))
+        .collect();
+    let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+    let started = Instant::now();
+    let sequential: Vec<_> = inputs.iter().map(|input| ancryptor::decode_bytes(input)).collect();
+    let sequential_time = started.elapsed();
+
+    let started = Instant::now();
+    let parallel = ancryptor::decode_batch_parallel(&inputs);
+    let parallel_time = started.elapsed();
+
+    assert_eq!(sequential, parallel);
+
+    println!("sequential: {:?}", sequential_time);
+    println!("parallel:   {:?}", parallel_time);
+    println!("speedup:    {:.2}x", sequential_time.as_secs_f64() / parallel_time.as_secs_f64());
+}
@@ -0,0 +1,111 @@
+// The engine behind `encode`, `encode_bytes` and `decode_bytes`: the
+// scalar `base64` engine, or `base64-simd` with the `simd` feature.
+// Both must produce byte-identical output and accept the same inputs.
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64::{
+    Engine as _,
+    engine::general_purpose::STANDARD as base64Engine
+};
+
+///
+/// Encodes `data` as standard, padded base64.
+///
+#[cfg(not(feature = "simd"))]
+pub(crate) fn encode(data: &[u8]) -> String {
+    base64Engine.encode(data)
+}
+
+///
+/// Encodes `data` as standard, padded base64.
+///
+#[cfg(feature = "simd")]
+pub(crate) fn encode(data: &[u8]) -> String {
+    base64_simd::STANDARD.encode_to_string(data)
+}
+
+///
+/// Decodes standard, padded base64, rejecting the same inputs as the
+/// `base64` engine.
+///
+#[cfg(not(feature = "simd"))]
+pub(crate) fn decode(from: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    base64Engine.decode(from)
+}
+
+///
+/// Decodes standard, padded base64, rejecting the same inputs as the
+/// `base64` engine.
+///
+/// `base64-simd` errors carry no detail, so a failure is decoded again
+/// by the scalar engine to report where the input went wrong.
+///
+#[cfg(feature = "simd")]
+pub(crate) fn decode(from: &str) -> Result<Vec<u8>, base64::DecodeError> {
+    base64_simd::STANDARD.decode_to_vec(from).or_else(|_| base64Engine.decode(from))
+}
+
+//
+// T E S T S
+//
+#[cfg(all(test, feature = "simd"))]
+mod tests {
+    use super::*;
+
+    // xorshift64, so the "random" inputs are the same on every run.
+    fn pseudo_random_bytes(seed: &mut u64, len: usize) -> Vec<u8> {
+        (0..len)
+            .map(|_| {
+                *seed ^= *seed << 13;
+                *seed ^= *seed >> 7;
+                *seed ^= *seed << 17;
+                *seed as u8
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_simd_encode_matches_scalar() {
+        let mut seed = 0x5EED;
+
+        for len in (0..200).chain([1024, 4096, 65_537]) {
+            let data = pseudo_random_bytes(&mut seed, len);
+
+            assert_eq!(encode(&data), base64Engine.encode(&data), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_simd_decode_matches_scalar() {
+        let mut seed = 0xDEC0DE;
+
+        for len in 0..200 {
+            let data = pseudo_random_bytes(&mut seed, len);
+            let encoded = base64Engine.encode(&data);
+
+            assert_eq!(decode(&encoded), Ok(data));
+        }
+    }
+
+    #[test]
+    fn test_simd_decode_rejects_what_scalar_rejects() {
+        let mut seed = 0xBAD;
+        let symbols = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/=-_ \n";
+
+        for len in 0..400 {
+            let text: String = pseudo_random_bytes(&mut seed, len % 40)
+                .into_iter()
+                .map(|byte| symbols[byte as usize % symbols.len()] as char)
+                .collect();
+
+            assert_eq!(decode(&text), base64Engine.decode(&text), "{:?}", text);
+        }
+
+        // non-canonical trailing bits and missing padding
+        for text in ["aGk", "aGl=", "aR==", "aGk==", "=aGk", "aGk=aGk="] {
+            assert_eq!(decode(text), base64Engine.decode(text), "{:?}", text);
+        }
+    }
+}
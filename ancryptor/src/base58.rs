@@ -0,0 +1,89 @@
+use alloc::string::{String, ToString};
+use alloc::vec::Vec;
+
+use crate::error::DecodeError;
+
+///
+/// Encodes raw bytes as Base58 with the Bitcoin alphabet, which leaves
+/// out the look-alike characters `0`, `O`, `I` and `l` and needs no
+/// padding, for identifiers people read or type.
+///
+/// Each leading zero byte becomes a leading `1`. Only available with the
+/// `base58` feature.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::encode_base58(b"hello"), "Cn8eVZg");
+/// assert_eq!(ancryptor::encode_base58(&[0, 0, 1]), "112");
+/// ```
+pub fn encode_base58(data: &[u8]) -> String {
+    bs58::encode(data).into_string()
+}
+
+///
+/// Decodes Bitcoin-alphabet Base58 back into raw bytes, turning each
+/// leading `1` back into a zero byte.
+///
+/// Only available with the `base58` feature.
+///
+/// ## Example
+/// ```
+/// use ancryptor::DecodeError;
+///
+/// assert_eq!(ancryptor::decode_base58("Cn8eVZg").unwrap(), b"hello");
+/// assert!(matches!(ancryptor::decode_base58("0OIl"), Err(DecodeError::InvalidBase58(_))));
+/// ```
+pub fn decode_base58(from: &str) -> Result<Vec<u8>, DecodeError> {
+    bs58::decode(from)
+        .into_vec()
+        .map_err(|error| DecodeError::InvalidBase58(error.to_string()))
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // From the Bitcoin Core base58 test vectors.
+    static VECTORS: [(&str, &str); 6] = [
+        ("", ""),
+        ("61", "2g"),
+        ("626262", "a3gV"),
+        ("73696d706c792061206c6f6e6720737472696e67", "2cFupjhnEsSn59qHXstmK2ffpLv2"),
+        ("00eb15231dfceb60925886b67d065299925915aeb172c06647", "1NS17iag9jJgTHD1VXjvLCEnZuQ3rJDE9L"),
+        ("00000000000000000000", "1111111111"),
+    ];
+
+    #[test]
+    fn test_encode_base58_known_vectors() {
+        for (hex, encoded) in VECTORS {
+            assert_eq!(encode_base58(&crate::decode_hex(hex).unwrap()), encoded, "{}", hex);
+        }
+    }
+
+    #[test]
+    fn test_decode_base58_known_vectors() {
+        for (hex, encoded) in VECTORS {
+            assert_eq!(decode_base58(encoded), Ok(crate::decode_hex(hex).unwrap()), "{}", encoded);
+        }
+    }
+
+    #[test]
+    fn test_base58_keeps_leading_zero_bytes() {
+        let data = [0, 0, 0, 0xFF, 0x00, 0x10];
+        let encoded = encode_base58(&data);
+
+        assert!(encoded.starts_with("111"));
+        assert!(!encoded[3..].starts_with('1'));
+        assert_eq!(decode_base58(&encoded), Ok(data.to_vec()));
+    }
+
+    #[test]
+    fn test_decode_base58_rejects_ambiguous_characters() {
+        for invalid in ["0", "O", "I", "l", "2g=", "2g 2g"] {
+            assert!(matches!(decode_base58(invalid), Err(DecodeError::InvalidBase58(_))), "{:?}", invalid);
+        }
+    }
+}
@@ -0,0 +1,105 @@
+use std::collections::HashMap;
+
+#[cfg(feature = "rayon")]
+use rayon::prelude::*;
+
+#[cfg(feature = "rayon")]
+use crate::error::DecodeError;
+
+///
+/// Decodes many independent base64 strings across all cores.
+///
+/// The results line up with `inputs`: the `n`th result belongs to the
+/// `n`th input, whatever order the work actually finished in.
+///
+/// Only available with the `rayon` feature.
+///
+/// ## Example
+/// ```
+/// let decoded = ancryptor::decode_batch_parallel(&["aGk=", "!!"]);
+///
+/// assert_eq!(decoded[0], Ok(b"hi".to_vec()));
+/// assert!(decoded[1].is_err());
+/// ```
+#[cfg(feature = "rayon")]
+pub fn decode_batch_parallel(inputs: &[&str]) -> Vec<Result<Vec<u8>, DecodeError>> {
+    inputs.par_iter()
+        .map(|input| crate::decode_bytes(input))
+        .collect()
+}
+
+///
+/// Encodes every input like [`crate::encode_bytes`], but encodes each
+/// distinct input only once and reuses the result for its repeats.
+///
+/// The results line up with `inputs`, duplicates included. Worth it for
+/// lists with many repeated blobs; for mostly unique inputs the hashing
+/// is pure overhead.
+///
+/// ## Example
+/// ```
+/// let encoded = ancryptor::encode_batch_dedup(&[b"hi", b"there", b"hi"]);
+///
+/// assert_eq!(encoded, ["aGk=", "dGhlcmU=", "aGk="]);
+/// ```
+pub fn encode_batch_dedup(inputs: &[&[u8]]) -> Vec<String> {
+    let mut encoded_by_input: HashMap<&[u8], String> = HashMap::new();
+
+    inputs.iter()
+        .map(|input| {
+            encoded_by_input.entry(input)
+                .or_insert_with(|| crate::encode_bytes(input))
+                .clone()
+        })
+        .collect()
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_batch_dedup_keeps_positions() {
+        let inputs: [&[u8]; 6] = [b"logo.png", b"icon.svg", b"logo.png", b"", b"icon.svg", b"logo.png"];
+
+        let encoded = encode_batch_dedup(&inputs);
+
+        assert_eq!(encoded.len(), inputs.len());
+        for (input, output) in inputs.iter().zip(&encoded) {
+            assert_eq!(output, &crate::encode_bytes(input));
+        }
+        assert_eq!(encoded[0], encoded[2]);
+        assert_ne!(encoded[0], encoded[1]);
+    }
+
+    #[test]
+    fn test_encode_batch_dedup_empty_list() {
+        assert!(encode_batch_dedup(&[]).is_empty());
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_decode_batch_parallel_preserves_order() {
+        let inputs: Vec<String> = (0..1_000).map(|index| crate::encode(&index.to_string())).collect();
+        let inputs: Vec<&str> = inputs.iter().map(String::as_str).collect();
+
+        let decoded = decode_batch_parallel(&inputs);
+
+        for (index, result) in decoded.into_iter().enumerate() {
+            assert_eq!(result.unwrap(), index.to_string().into_bytes());
+        }
+    }
+
+    #[cfg(feature = "rayon")]
+    #[test]
+    fn test_decode_batch_parallel_reports_errors_in_place() {
+        let decoded = decode_batch_parallel(&["aGk=", "dfoiuerw892", "aGk="]);
+
+        assert!(decoded[0].is_ok());
+        assert!(matches!(decoded[1], Err(DecodeError::InvalidBase64(_))));
+        assert!(decoded[2].is_ok());
+    }
+}
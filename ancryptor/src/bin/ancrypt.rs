@@ -0,0 +1,258 @@
+//!
+//! Command line front-end for the `ancryptor` crate. Reads its input
+//! from stdin and writes the result to stdout.
+//!
+//! ## Examples
+//! ```
+//! $ cd ancryptor/
+//! $ export ANCRYPTOR_KEY=$(head -c 32 /dev/urandom | base64)
+//! $ cargo run --bin ancrypt -- seal < notes.txt > notes.sealed
+//! $ cargo run --bin ancrypt -- open --key-file key.b64 < notes.sealed
+//! $ cargo run --bin ancrypt -- encode < file.txt
+//! $ cargo run --bin ancrypt -- decode --url-safe < token.txt
+//! $ cargo run --bin ancrypt -- decode --hexdump < payload.b64
+//! ```
+//!
+//! Keys are 32 bytes, base64-encoded. They are read, in order of
+//! preference, from `--key-file <path>`, `--key <base64>` or the
+//! `ANCRYPTOR_KEY` environment variable. Passing `--key` works but warns,
+//! because anything on the command line is visible in the process list.
+//! `encode` and `decode` need no key, they only base64-encode or decode
+//! their input; with `--url-safe` they use the url-safe alphabet (`-` and
+//! `_`). Both fail on empty input.
+//!
+//! `--hexdump` prints the output as `hexdump -C` style rows instead of
+//! raw bytes, which is handy for binary payloads.
+//!
+
+use std::env;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::process::ExitCode;
+
+use ancryptor::{Envelope, EncodingKind};
+
+static KEY_ENV_VAR: &str = "ANCRYPTOR_KEY";
+static USAGE: &str = "usage: ancrypt <seal|open|encode|decode> [--key-file <path> | --key <base64>] [--url-safe] [--hexdump]";
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    Seal,
+    Open,
+    Encode,
+    Decode,
+}
+
+#[derive(Debug, PartialEq)]
+enum KeySource {
+    File(String),
+    Inline(String),
+    Env,
+}
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    command: Command,
+    key_source: KeySource,
+    url_safe: bool,
+    hexdump: bool,
+}
+
+///
+/// Parses the arguments that follow the binary name.
+///
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut command = None;
+    let mut key_source = KeySource::Env;
+    let mut url_safe = false;
+    let mut hexdump = false;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "seal" => command = Some(Command::Seal),
+            "open" => command = Some(Command::Open),
+            "encode" => command = Some(Command::Encode),
+            "decode" => command = Some(Command::Decode),
+            "--url-safe" => url_safe = true,
+            "--hexdump" => hexdump = true,
+            "--key-file" => {
+                let path = args.next().ok_or("--key-file needs a path")?;
+                key_source = KeySource::File(path.to_owned());
+            }
+            "--key" => {
+                let key = args.next().ok_or("--key needs a value")?;
+                key_source = KeySource::Inline(key.to_owned());
+            }
+            unknown => return Err(format!("unknown argument '{}'", unknown)),
+        }
+    }
+
+    match command {
+        Some(Command::Seal | Command::Open) if url_safe => Err("--url-safe only applies to encode and decode".to_owned()),
+        Some(command) => Ok(Args { command, key_source, url_safe, hexdump }),
+        None => Err(USAGE.to_owned()),
+    }
+}
+
+///
+/// Reads the base64 key from its source and checks it is 32 bytes.
+///
+fn resolve_key(key_source: &KeySource, env_key: Option<String>) -> Result<[u8; 32], String> {
+    let encoded_key = match key_source {
+        KeySource::File(path) => fs::read_to_string(path)
+            .map_err(|error| format!("cannot read key file '{}': {}", path, error))?,
+        KeySource::Inline(key) => key.to_owned(),
+        KeySource::Env => env_key.ok_or(format!("no key given: use --key-file or set {}", KEY_ENV_VAR))?,
+    };
+
+    let key = ancryptor::decode_tolerant(&encoded_key)
+        .map_err(|error| format!("key is not valid base64: {}", error))?;
+
+    key.try_into().map_err(|key: Vec<u8>| format!("key must be 32 bytes, got {}", key.len()))
+}
+
+///
+/// Base64-decodes `input`, ignoring whitespace such as a trailing newline.
+///
+fn decode_input(input: &[u8], url_safe: bool) -> Result<Vec<u8>, String> {
+    let encoded = String::from_utf8_lossy(input);
+
+    let decoded = if url_safe {
+        let compact: String = encoded.split_ascii_whitespace().collect();
+        ancryptor::decode_as(&compact, EncodingKind::Base64UrlSafe)
+    } else {
+        ancryptor::decode_tolerant(&encoded)
+    };
+
+    decoded.map_err(|error| error.to_string())
+}
+
+fn run(args: &Args, input: &[u8]) -> Result<Vec<u8>, String> {
+    if matches!(args.command, Command::Encode | Command::Decode) && input.trim_ascii().is_empty() {
+        return Err("no input on stdin".to_owned());
+    }
+
+    let output = match args.command {
+        Command::Seal => {
+            let key = resolve_key(&args.key_source, env::var(KEY_ENV_VAR).ok())?;
+            format!("{}\n", Envelope::seal(input, &key)).into_bytes()
+        }
+        Command::Open => {
+            let key = resolve_key(&args.key_source, env::var(KEY_ENV_VAR).ok())?;
+            let sealed = String::from_utf8_lossy(input);
+            Envelope::open(sealed.trim(), &key).map_err(|error| error.to_string())?
+        }
+        Command::Encode if args.url_safe => format!("{}\n", ancryptor::encode_as(input, EncodingKind::Base64UrlSafe)).into_bytes(),
+        Command::Encode => format!("{}\n", ancryptor::encode_bytes(input)).into_bytes(),
+        Command::Decode => decode_input(input, args.url_safe)?,
+    };
+
+    if args.hexdump {
+        return Ok(ancryptor::hexdump(&output).into_bytes());
+    }
+
+    Ok(output)
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let KeySource::Inline(_) = args.key_source {
+        eprintln!("warning: --key exposes the key in the process list, prefer --key-file or {}", KEY_ENV_VAR);
+    }
+
+    let mut input = Vec::new();
+    if let Err(error) = io::stdin().read_to_end(&mut input) {
+        eprintln!("cannot read stdin: {}", error);
+        return ExitCode::FAILURE;
+    }
+
+    match run(&args, &input) {
+        Ok(output) => {
+            io::stdout().write_all(&output).expect("cannot write to stdout");
+            ExitCode::SUCCESS
+        }
+        Err(message) => {
+            eprintln!("{}", message);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_defaults_to_env_key() {
+        let parsed = parse_args(&args(&["seal"])).unwrap();
+
+        assert_eq!(parsed, Args { command: Command::Seal, key_source: KeySource::Env, url_safe: false, hexdump: false });
+    }
+
+    #[test]
+    fn test_parse_args_key_file() {
+        let parsed = parse_args(&args(&["open", "--key-file", "key.b64"])).unwrap();
+
+        assert_eq!(parsed.key_source, KeySource::File("key.b64".to_owned()));
+    }
+
+    #[test]
+    fn test_parse_args_decode_with_hexdump() {
+        let parsed = parse_args(&args(&["decode", "--hexdump"])).unwrap();
+
+        assert_eq!(parsed.command, Command::Decode);
+        assert!(parsed.hexdump);
+    }
+
+    #[test]
+    fn test_parse_args_encode_url_safe() {
+        let parsed = parse_args(&args(&["encode", "--url-safe"])).unwrap();
+
+        assert_eq!(parsed.command, Command::Encode);
+        assert!(parsed.url_safe);
+        assert!(parse_args(&args(&["seal", "--url-safe"])).is_err());
+    }
+
+    #[test]
+    fn test_parse_args_rejects_missing_command_and_unknown_flags() {
+        assert!(parse_args(&args(&[])).is_err());
+        assert!(parse_args(&args(&["seal", "--verbose"])).is_err());
+        assert!(parse_args(&args(&["seal", "--key-file"])).is_err());
+    }
+
+    #[test]
+    fn test_resolve_key_from_env() {
+        let key = resolve_key(&KeySource::Env, Some(ancryptor::encode_bytes(&[5; 32])));
+
+        assert_eq!(key, Ok([5; 32]));
+    }
+
+    #[test]
+    fn test_resolve_key_wrong_length() {
+        let key = resolve_key(&KeySource::Inline(ancryptor::encode_bytes(&[5; 16])), None);
+
+        assert_eq!(key, Err("key must be 32 bytes, got 16".to_owned()));
+    }
+
+    #[test]
+    fn test_resolve_key_missing() {
+        assert!(resolve_key(&KeySource::Env, None).is_err());
+    }
+}
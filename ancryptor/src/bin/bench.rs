@@ -0,0 +1,142 @@
+//!
+//! Encode throughput benchmark for `ancryptor::encode_bytes`.
+//!
+//! Encodes a generated buffer `--iterations` times and prints the
+//! throughput, so it can be compared with the system `base64` tool on
+//! the same machine and the same amount of data.
+//!
+//! ## Examples
+//! ```
+//! $ cd ancryptor/
+//! $ cargo run --release --bin bench -- --size 67108864 --iterations 10
+//! $ head -c 67108864 /dev/urandom > /tmp/bench.bin && time base64 -w 0 /tmp/bench.bin > /dev/null
+//! ```
+//!
+
+use std::env;
+use std::process::ExitCode;
+use std::time::{Duration, Instant};
+
+static DEFAULT_SIZE: usize = 16 * 1024 * 1024;
+static DEFAULT_ITERATIONS: usize = 5;
+static USAGE: &str = "usage: bench [--size <bytes>] [--iterations <count>]";
+
+#[derive(Debug, PartialEq)]
+struct Args {
+    size: usize,
+    iterations: usize,
+}
+
+///
+/// Parses the arguments that follow the binary name.
+///
+fn parse_args(args: &[String]) -> Result<Args, String> {
+    let mut size = DEFAULT_SIZE;
+    let mut iterations = DEFAULT_ITERATIONS;
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--size" => size = parse_count(args.next(), "--size")?,
+            "--iterations" => iterations = parse_count(args.next(), "--iterations")?,
+            unknown => return Err(format!("unknown argument '{}'\n{}", unknown, USAGE)),
+        }
+    }
+
+    Ok(Args { size, iterations })
+}
+
+fn parse_count(value: Option<&String>, flag: &str) -> Result<usize, String> {
+    let value = value.ok_or(format!("{} needs a value", flag))?;
+
+    match value.parse() {
+        Ok(count) if count > 0 => Ok(count),
+        _ => Err(format!("{} must be a positive number, got '{}'", flag, value)),
+    }
+}
+
+///
+/// Fills a buffer with a fixed xorshift sequence, so every run encodes the
+/// same data without paying for a random number generator.
+///
+fn generate_buffer(size: usize) -> Vec<u8> {
+    let mut state: u64 = 0x9E37_79B9_7F4A_7C15;
+
+    (0..size).map(|_| {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state as u8
+    }).collect()
+}
+
+fn megabytes_per_second(bytes: usize, elapsed: Duration) -> f64 {
+    bytes as f64 / (1024.0 * 1024.0) / elapsed.as_secs_f64()
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let args = match parse_args(&args) {
+        Ok(args) => args,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let buffer = generate_buffer(args.size);
+    let mut encoded_len = 0;
+
+    let started = Instant::now();
+    for _ in 0..args.iterations {
+        // Keep the output alive so the encoding cannot be optimised away.
+        encoded_len += ancryptor::encode_bytes(&buffer).len();
+    }
+    let elapsed = started.elapsed();
+
+    let total = args.size * args.iterations;
+    println!("encoded {} bytes x {} iterations in {:.3?}", args.size, args.iterations, elapsed);
+    println!("output: {} characters", encoded_len);
+    println!("throughput: {:.1} MB/s", megabytes_per_second(total, elapsed));
+
+    ExitCode::SUCCESS
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Vec<String> {
+        args.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_parse_args_defaults() {
+        assert_eq!(parse_args(&args(&[])).unwrap(), Args { size: DEFAULT_SIZE, iterations: DEFAULT_ITERATIONS });
+    }
+
+    #[test]
+    fn test_parse_args_size_and_iterations() {
+        let parsed = parse_args(&args(&["--size", "1024", "--iterations", "3"])).unwrap();
+
+        assert_eq!(parsed, Args { size: 1024, iterations: 3 });
+    }
+
+    #[test]
+    fn test_parse_args_rejects_bad_counts() {
+        assert!(parse_args(&args(&["--size"])).is_err());
+        assert!(parse_args(&args(&["--size", "0"])).is_err());
+        assert!(parse_args(&args(&["--iterations", "many"])).is_err());
+        assert!(parse_args(&args(&["--verbose"])).is_err());
+    }
+
+    #[test]
+    fn test_generate_buffer_is_deterministic() {
+        assert_eq!(generate_buffer(64), generate_buffer(64));
+        assert_eq!(generate_buffer(64).len(), 64);
+    }
+}
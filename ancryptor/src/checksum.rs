@@ -0,0 +1,98 @@
+use crate::encoding::{decode_as, encode_as, EncodingKind};
+use crate::error::AncryptorError;
+
+// The CRC-32 is appended to the data as 4 big-endian bytes before encoding.
+const CHECKSUM_LEN: usize = 4;
+
+///
+/// Appends a CRC-32 of `data` and encodes the result in the given format,
+/// so that [`safe_decode`] can detect accidental corruption such as a
+/// mistyped or truncated string.
+///
+/// A CRC only catches accidents. It does not stop anyone from changing
+/// the data on purpose; sign it for that, see [`crate::encode_and_sign`].
+///
+/// ## Example
+/// ```
+/// use ancryptor::{safe_decode, safe_encode, EncodingKind};
+///
+/// let encoded = safe_encode(b"hello", EncodingKind::Hex);
+///
+/// assert_eq!(encoded, "68656c6c6f3610a686");
+/// assert_eq!(safe_decode(&encoded, EncodingKind::Hex).unwrap(), b"hello");
+/// ```
+pub fn safe_encode(data: &[u8], kind: EncodingKind) -> String {
+    let mut checked = Vec::with_capacity(data.len() + CHECKSUM_LEN);
+    checked.extend_from_slice(data);
+    checked.extend_from_slice(&crc32fast::hash(data).to_be_bytes());
+
+    encode_as(&checked, kind)
+}
+
+///
+/// Decodes the output of [`safe_encode`], verifying and stripping the
+/// CRC-32.
+///
+/// Returns [`AncryptorError::ChecksumMismatch`] if the data does not
+/// match its checksum.
+///
+pub fn safe_decode(s: &str, kind: EncodingKind) -> Result<Vec<u8>, AncryptorError> {
+    let mut data = decode_as(s, kind)?;
+
+    if data.len() < CHECKSUM_LEN {
+        return Err(AncryptorError::ChecksumMismatch);
+    }
+
+    let checksum = data.split_off(data.len() - CHECKSUM_LEN);
+    if crc32fast::hash(&data).to_be_bytes() != checksum.as_slice() {
+        return Err(AncryptorError::ChecksumMismatch);
+    }
+
+    Ok(data)
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flip_first_character(encoded: &str, replacement: char) -> String {
+        let mut flipped = encoded.to_owned();
+        flipped.replace_range(..1, &replacement.to_string());
+        assert_ne!(flipped, encoded);
+        flipped
+    }
+
+    #[test]
+    fn test_safe_round_trip_every_kind() {
+        for kind in [EncodingKind::Base64, EncodingKind::Base64UrlSafe, EncodingKind::Base32, EncodingKind::Hex] {
+            let encoded = safe_encode(b"hello_world_from_rust", kind);
+
+            assert_eq!(safe_decode(&encoded, kind).unwrap(), b"hello_world_from_rust", "{:?}", kind);
+        }
+    }
+
+    #[test]
+    fn test_safe_decode_base64_flipped_character() {
+        let encoded = safe_encode(b"hello_world_from_rust", EncodingKind::Base64);
+        let flipped = flip_first_character(&encoded, 'b');
+
+        assert_eq!(safe_decode(&flipped, EncodingKind::Base64), Err(AncryptorError::ChecksumMismatch));
+    }
+
+    #[test]
+    fn test_safe_decode_hex_flipped_character() {
+        let encoded = safe_encode(b"hello_world_from_rust", EncodingKind::Hex);
+        let flipped = flip_first_character(&encoded, '7');
+
+        assert_eq!(safe_decode(&flipped, EncodingKind::Hex), Err(AncryptorError::ChecksumMismatch));
+    }
+
+    #[test]
+    fn test_safe_decode_too_short_for_checksum() {
+        assert_eq!(safe_decode("abcd", EncodingKind::Hex), Err(AncryptorError::ChecksumMismatch));
+        assert!(matches!(safe_decode("zz", EncodingKind::Hex), Err(AncryptorError::Decode(_))));
+    }
+}
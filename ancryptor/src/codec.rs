@@ -0,0 +1,211 @@
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex, OnceLock};
+
+use base64::{
+    Engine as _,
+    alphabet::Alphabet,
+    engine::{general_purpose::STANDARD, DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig},
+};
+
+use crate::encoding::{decode_as, encode_as, EncodingKind};
+use crate::error::{CodecError, DecodeError};
+
+///
+/// Something that turns bytes into text and back.
+///
+/// Codecs must be `Send + Sync` so they can be shared through the
+/// registry, see [`register_codec`].
+///
+pub trait Codec: Send + Sync {
+    fn encode(&self, data: &[u8]) -> String;
+    fn decode(&self, from: &str) -> Result<Vec<u8>, DecodeError>;
+}
+
+impl Codec for EncodingKind {
+    fn encode(&self, data: &[u8]) -> String {
+        encode_as(data, *self)
+    }
+
+    fn decode(&self, from: &str) -> Result<Vec<u8>, DecodeError> {
+        decode_as(from, *self)
+    }
+}
+
+///
+/// A base64 codec over a custom 64-character alphabet, for talking to
+/// systems that do not use the standard one.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{Codec, CustomCodec};
+///
+/// // The standard alphabet, lowercase letters first.
+/// let alphabet = "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789+/";
+/// let codec = CustomCodec::new(alphabet, true).unwrap();
+///
+/// assert_eq!(codec.encode(b"hi"), "AgK=");
+/// assert_eq!(codec.decode("AgK=").unwrap(), b"hi");
+/// ```
+pub struct CustomCodec {
+    engine: GeneralPurpose,
+}
+
+impl CustomCodec {
+    ///
+    /// Builds a codec from `alphabet`, which must hold exactly 64 unique
+    /// printable ASCII characters and must not contain the `=` padding
+    /// character. `padded` controls whether output is padded with `=`
+    /// and whether padding is required when decoding.
+    ///
+    pub fn new(alphabet: &str, padded: bool) -> Result<CustomCodec, CodecError> {
+        let alphabet = Alphabet::new(alphabet).map_err(|_| CodecError::InvalidAlphabet)?;
+
+        let padding_mode = if padded {
+            DecodePaddingMode::RequireCanonical
+        } else {
+            DecodePaddingMode::RequireNone
+        };
+        let config = GeneralPurposeConfig::new()
+            .with_encode_padding(padded)
+            .with_decode_padding_mode(padding_mode);
+
+        Ok(CustomCodec { engine: GeneralPurpose::new(&alphabet, config) })
+    }
+
+    ///
+    /// The standard, padded base64 codec, which behaves exactly like
+    /// [`crate::encode_bytes`] and [`crate::decode_bytes`] (apart from the
+    /// byte-order mark those skip).
+    ///
+    /// ## Example
+    /// ```
+    /// use ancryptor::{Codec, CustomCodec};
+    ///
+    /// assert_eq!(CustomCodec::standard().encode(b"hi"), ancryptor::encode_bytes(b"hi"));
+    /// ```
+    pub const fn standard() -> CustomCodec {
+        CustomCodec { engine: STANDARD }
+    }
+}
+
+impl Codec for CustomCodec {
+    fn encode(&self, data: &[u8]) -> String {
+        self.engine.encode(data)
+    }
+
+    fn decode(&self, from: &str) -> Result<Vec<u8>, DecodeError> {
+        self.engine.decode(from).map_err(DecodeError::InvalidBase64)
+    }
+}
+
+type CodecRegistry = Mutex<HashMap<String, Arc<dyn Codec>>>;
+
+fn registry() -> &'static CodecRegistry {
+    static REGISTRY: OnceLock<CodecRegistry> = OnceLock::new();
+    REGISTRY.get_or_init(|| Mutex::new(HashMap::new()))
+}
+
+///
+/// Registers `codec` under `name` for the lifetime of the process, so it
+/// can be looked up later with [`codec_by_name`]. Registering a name
+/// again replaces the previous codec.
+///
+/// The registry is shared by all threads.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{codec_by_name, register_codec, Codec, EncodingKind};
+///
+/// register_codec("debug", Box::new(EncodingKind::Hex));
+///
+/// let codec = codec_by_name("debug").unwrap();
+/// assert_eq!(codec.encode(b"hi"), "6869");
+/// ```
+pub fn register_codec(name: &str, codec: Box<dyn Codec>) {
+    registry().lock()
+        .expect("Codec registry lock poisoned")
+        .insert(name.to_owned(), Arc::from(codec));
+}
+
+///
+/// Returns the codec registered under `name`, if there is one.
+///
+pub fn codec_by_name(name: &str) -> Option<Arc<dyn Codec>> {
+    registry().lock()
+        .expect("Codec registry lock poisoned")
+        .get(name)
+        .cloned()
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::thread;
+
+    static REVERSED_ALPHABET: &str = "/+9876543210zyxwvutsrqponmlkjihgfedcbaZYXWVUTSRQPONMLKJIHGFEDCBA";
+
+    #[test]
+    fn test_register_and_retrieve_custom_alphabet_codec() {
+        register_codec("test-reversed", Box::new(CustomCodec::new(REVERSED_ALPHABET, false).unwrap()));
+
+        let codec = codec_by_name("test-reversed").expect("codec was registered");
+        let encoded = codec.encode(b"hello_world_from_rust");
+
+        assert_ne!(encoded, crate::encode("hello_world_from_rust"));
+        assert_eq!(codec.decode(&encoded).unwrap(), b"hello_world_from_rust");
+    }
+
+    #[test]
+    fn test_unknown_codec_name() {
+        assert!(codec_by_name("test-never-registered").is_none());
+    }
+
+    #[test]
+    fn test_registry_is_shared_between_threads() {
+        let handles: Vec<_> = (0..8).map(|index| {
+            thread::spawn(move || register_codec(&format!("test-thread-{}", index), Box::new(EncodingKind::Base32)))
+        }).collect();
+
+        for handle in handles {
+            handle.join().unwrap();
+        }
+
+        for index in 0..8 {
+            let codec = codec_by_name(&format!("test-thread-{}", index)).unwrap();
+            assert_eq!(codec.encode(b"f"), "MY======");
+        }
+    }
+
+    #[test]
+    fn test_rotated_alphabet_round_trips_differently_from_standard() {
+        let standard_alphabet = crate::alphabet_for(EncodingKind::Base64);
+        let rotated_alphabet = format!("{}{}", &standard_alphabet[1..], &standard_alphabet[..1]);
+        let codec = CustomCodec::new(&rotated_alphabet, true).unwrap();
+
+        let encoded = codec.encode(b"hello_world_from_rust");
+
+        assert_ne!(encoded, crate::encode_bytes(b"hello_world_from_rust"));
+        assert_eq!(codec.decode(&encoded).unwrap(), b"hello_world_from_rust");
+        assert_eq!(codec.encode(&[0x00, 0x00, 0x00]), "BBBB");
+    }
+
+    #[test]
+    fn test_standard_codec_matches_free_functions() {
+        let codec = CustomCodec::standard();
+
+        assert_eq!(codec.encode(b"hello_world_from_rust"), crate::encode_bytes(b"hello_world_from_rust"));
+        assert_eq!(codec.decode("aGk=").unwrap(), crate::decode_bytes("aGk=").unwrap());
+    }
+
+    #[test]
+    fn test_custom_codec_rejects_invalid_alphabets() {
+        let duplicated = REVERSED_ALPHABET.replace('A', "B");
+
+        assert!(matches!(CustomCodec::new("too short", true), Err(CodecError::InvalidAlphabet)));
+        assert!(matches!(CustomCodec::new(&duplicated, true), Err(CodecError::InvalidAlphabet)));
+    }
+}
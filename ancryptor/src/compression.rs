@@ -0,0 +1,98 @@
+use std::io::{Read, Write};
+
+use base64::{
+    Engine as _,
+    engine::general_purpose::STANDARD as base64Engine
+};
+use flate2::Compression;
+use flate2::read::GzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::error::DecodeError;
+
+///
+/// Gzips `to` and encodes the result as standard, padded base64, which
+/// is much shorter than [`crate::encode`] for large, repetitive text.
+///
+/// Short inputs come out longer: the gzip header and trailer alone take
+/// 18 bytes. Only available with the `compression` feature.
+///
+/// ## Example
+/// ```
+/// let text = "ancryptor ".repeat(1000);
+/// let encoded = ancryptor::encode_compressed(&text);
+///
+/// assert!(encoded.len() < ancryptor::encode(&text).len() / 10);
+/// assert_eq!(ancryptor::decode_compressed(&encoded).unwrap(), text);
+/// ```
+pub fn encode_compressed(to: &str) -> String {
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    // Writing to a `Vec` cannot fail.
+    encoder.write_all(to.as_bytes()).expect("gzip into memory failed");
+    let compressed = encoder.finish().expect("gzip into memory failed");
+
+    base64Engine.encode(compressed)
+}
+
+///
+/// Decodes the output of [`encode_compressed`] back into the original
+/// `String`.
+///
+/// The decompressed size is not limited, so a small crafted input can
+/// expand to a lot of memory; only use it on trusted input. Only
+/// available with the `compression` feature.
+///
+pub fn decode_compressed(from: &str) -> Result<String, DecodeError> {
+    let compressed = crate::decode_bytes(from)?;
+
+    let mut decompressed = Vec::new();
+    GzDecoder::new(compressed.as_slice())
+        .read_to_end(&mut decompressed)
+        .map_err(|error| DecodeError::InvalidGzip(error.to_string()))?;
+
+    String::from_utf8(decompressed).map_err(DecodeError::InvalidUtf8)
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_compressed_repetitive_text_is_much_smaller() {
+        let text = "hello_world_from_rust ".repeat(500);
+        assert!(text.len() >= 10 * 1024);
+
+        let encoded = encode_compressed(&text);
+
+        assert!(encoded.len() * 20 < crate::encode(&text).len(), "{} bytes", encoded.len());
+    }
+
+    #[test]
+    fn test_compressed_round_trip() {
+        let text = "hello_world_from_rust ".repeat(500);
+
+        assert_eq!(decode_compressed(&encode_compressed(&text)), Ok(text));
+        assert_eq!(decode_compressed(&encode_compressed("")), Ok("".to_owned()));
+        assert_eq!(decode_compressed(&encode_compressed("héllo")), Ok("héllo".to_owned()));
+    }
+
+    #[test]
+    fn test_decode_compressed_rejects_plain_base64() {
+        let plain = crate::encode("hello_world_from_rust");
+
+        assert!(matches!(decode_compressed(&plain), Err(DecodeError::InvalidGzip(_))));
+        assert!(matches!(decode_compressed("!!"), Err(DecodeError::InvalidBase64(_))));
+    }
+
+    #[test]
+    fn test_decode_compressed_rejects_truncated_data() {
+        let encoded = encode_compressed(&"hello_world_from_rust ".repeat(500));
+        let mut compressed = crate::decode_bytes(&encoded).unwrap();
+        compressed.truncate(compressed.len() - 4);
+
+        assert!(matches!(decode_compressed(&crate::encode_bytes(&compressed)), Err(DecodeError::InvalidGzip(_))));
+    }
+}
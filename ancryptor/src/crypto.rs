@@ -0,0 +1,509 @@
+// @See AES-GCM, HMAC, HKDF and Argon2:
+//  - https://docs.rs/aes-gcm/latest/aes_gcm/
+//  - https://docs.rs/hmac/latest/hmac/
+//  - https://www.rfc-editor.org/rfc/rfc5869
+//  - https://www.rfc-editor.org/rfc/rfc9106
+
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm,
+    Nonce,
+};
+use argon2::Argon2;
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use crate::error::CryptoError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+// AES-GCM uses 96-bit nonces and appends a 128-bit authentication tag.
+pub(crate) const NONCE_LEN: usize = 12;
+pub(crate) const TAG_LEN: usize = 16;
+
+// Password-encrypted payloads start with the Argon2id salt.
+const PASSWORD_SALT_LEN: usize = 16;
+
+///
+/// Encrypts `plaintext` with AES-256-GCM using a nonce derived from the
+/// plaintext itself, so identical plaintexts under the same key always
+/// produce identical ciphertexts (convergent encryption).
+///
+/// The nonce is the first 12 bytes of `HMAC-SHA256(key, plaintext)` and is
+/// prepended to the returned ciphertext. Use [`decrypt_convergent`] to
+/// reverse it.
+///
+/// ## Privacy
+///
+/// Determinism is the point, and also the cost: anyone who sees two
+/// ciphertexts can tell whether they hold the same plaintext, and anyone who
+/// can guess a plaintext can confirm the guess by encrypting it. Only use
+/// this where deduplication matters more than hiding equality.
+///
+/// ## Example
+/// ```
+/// let key = [7u8; 32];
+///
+/// let first = ancryptor::encrypt_convergent(b"blob", &key);
+/// let second = ancryptor::encrypt_convergent(b"blob", &key);
+///
+/// assert_eq!(first, second);
+/// ```
+pub fn encrypt_convergent(plaintext: &[u8], key: &[u8; 32]) -> Vec<u8> {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(plaintext);
+    let digest = mac.finalize().into_bytes();
+    let nonce = Nonce::from_slice(&digest[..NONCE_LEN]);
+
+    let cipher = Aes256Gcm::new(key.into());
+    let ciphertext = cipher.encrypt(nonce, plaintext).expect("AES-GCM encryption failed");
+
+    let mut output = Vec::with_capacity(NONCE_LEN + ciphertext.len());
+    output.extend_from_slice(nonce);
+    output.extend_from_slice(&ciphertext);
+    output
+}
+
+///
+/// Decrypts the output of [`encrypt_convergent`].
+///
+pub fn decrypt_convergent(ciphertext: &[u8], key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
+    decrypt_convergent_zeroizing(ciphertext, key).map(|plaintext| plaintext.to_vec())
+}
+
+///
+/// Like [`decrypt_convergent`], but the plaintext comes back in a
+/// [`Zeroizing`] wrapper that wipes it from memory when dropped.
+///
+/// ## Example
+/// ```
+/// let key = [7u8; 32];
+/// let ciphertext = ancryptor::encrypt_convergent(b"blob", &key);
+///
+/// let plaintext = ancryptor::decrypt_convergent_zeroizing(&ciphertext, &key).unwrap();
+///
+/// assert_eq!(plaintext.as_slice(), b"blob");
+/// ```
+pub fn decrypt_convergent_zeroizing(ciphertext: &[u8], key: &[u8; 32]) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+    if ciphertext.len() < NONCE_LEN + TAG_LEN {
+        return Err(CryptoError::InvalidLength);
+    }
+
+    let (nonce, ciphertext) = ciphertext.split_at(NONCE_LEN);
+    let cipher = Aes256Gcm::new(key.into());
+
+    cipher.decrypt(Nonce::from_slice(nonce), ciphertext)
+        .map(Zeroizing::new)
+        .map_err(|_| CryptoError::AuthenticationFailed)
+}
+
+///
+/// Encrypts `plaintext` with AES-256-GCM, authenticating `aad` (associated
+/// data) alongside it without encrypting it.
+///
+/// The associated data binds the ciphertext to its context, such as a
+/// record id: [`decrypt_aes_gcm_aad`] only succeeds when given the same
+/// `aad`, so a ciphertext cannot be replayed somewhere else.
+///
+/// Never reuse a `nonce` with the same key. The returned bytes are the
+/// ciphertext followed by the 16-byte tag; the nonce is not included.
+///
+/// ## Example
+/// ```
+/// let key = [1u8; 32];
+/// let nonce = [2u8; 12];
+///
+/// let ciphertext = ancryptor::encrypt_aes_gcm_aad(b"secret", &key, &nonce, b"record-7").unwrap();
+/// let plaintext = ancryptor::decrypt_aes_gcm_aad(&ciphertext, &key, &nonce, b"record-7").unwrap();
+///
+/// assert_eq!(plaintext, b"secret");
+/// ```
+pub fn encrypt_aes_gcm_aad(
+    plaintext: &[u8],
+    key: &[u8; 32],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    let cipher = Aes256Gcm::new(key.into());
+    let payload = Payload { msg: plaintext, aad };
+
+    cipher.encrypt(Nonce::from_slice(nonce), payload)
+        .map_err(|_| CryptoError::EncryptionFailed)
+}
+
+///
+/// Decrypts the output of [`encrypt_aes_gcm_aad`].
+///
+/// Returns [`CryptoError::AuthenticationFailed`] if the ciphertext, the
+/// key, the nonce or the associated data differ from encryption time.
+///
+pub fn decrypt_aes_gcm_aad(
+    ciphertext: &[u8],
+    key: &[u8; 32],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+) -> Result<Vec<u8>, CryptoError> {
+    decrypt_aes_gcm_aad_zeroizing(ciphertext, key, nonce, aad).map(|plaintext| plaintext.to_vec())
+}
+
+///
+/// Like [`decrypt_aes_gcm_aad`], but the plaintext comes back in a
+/// [`Zeroizing`] wrapper that wipes it from memory when dropped.
+///
+/// ## Example
+/// ```
+/// let (key, nonce) = ([1u8; 32], [2u8; 12]);
+/// let ciphertext = ancryptor::encrypt_aes_gcm_aad(b"secret", &key, &nonce, b"record-7").unwrap();
+///
+/// let plaintext = ancryptor::decrypt_aes_gcm_aad_zeroizing(&ciphertext, &key, &nonce, b"record-7").unwrap();
+///
+/// assert_eq!(plaintext.as_slice(), b"secret");
+/// ```
+pub fn decrypt_aes_gcm_aad_zeroizing(
+    ciphertext: &[u8],
+    key: &[u8; 32],
+    nonce: &[u8; NONCE_LEN],
+    aad: &[u8],
+) -> Result<Zeroizing<Vec<u8>>, CryptoError> {
+    if ciphertext.len() < TAG_LEN {
+        return Err(CryptoError::InvalidLength);
+    }
+
+    let cipher = Aes256Gcm::new(key.into());
+    let payload = Payload { msg: ciphertext, aad };
+
+    cipher.decrypt(Nonce::from_slice(nonce), payload)
+        .map(Zeroizing::new)
+        .map_err(|_| CryptoError::AuthenticationFailed)
+}
+
+///
+/// Encrypts `plaintext` with AES-256-GCM under a fresh random 96-bit
+/// nonce and returns `nonce || ciphertext+tag` as standard base64.
+///
+/// Encrypting the same text twice gives different results. Use
+/// [`decrypt`] to reverse it.
+///
+/// ## Example
+/// ```
+/// let key = [5u8; 32];
+///
+/// let ciphertext = ancryptor::encrypt("hello_world_from_rust", &key);
+///
+/// assert_ne!(ciphertext, ancryptor::encrypt("hello_world_from_rust", &key));
+/// assert_eq!(ancryptor::decrypt(&ciphertext, &key).unwrap(), "hello_world_from_rust");
+/// ```
+pub fn encrypt(plaintext: &str, key: &[u8; 32]) -> String {
+    let mut sealed = Vec::new();
+    seal_with_random_nonce(&mut sealed, plaintext, key);
+
+    crate::encode_bytes(&sealed)
+}
+
+///
+/// Decrypts the output of [`encrypt`].
+///
+/// Returns [`CryptoError::Malformed`] if `ciphertext` is not base64 or
+/// does not decrypt to UTF-8 text, [`CryptoError::InvalidLength`] if it is
+/// too short to hold a nonce and a tag, and
+/// [`CryptoError::AuthenticationFailed`] if it was tampered with or
+/// encrypted under another key.
+///
+pub fn decrypt(ciphertext: &str, key: &[u8; 32]) -> Result<String, CryptoError> {
+    decrypt_zeroizing(ciphertext, key).map(|plaintext| plaintext.as_str().to_owned())
+}
+
+///
+/// Like [`decrypt`], but the plaintext comes back in a [`Zeroizing`]
+/// wrapper that wipes it from memory when dropped.
+///
+/// Use it when the decrypted text is itself a secret, e.g. a token or a
+/// password, so that no copy outlives the caller's use of it.
+///
+/// ## Example
+/// ```
+/// let key = [5u8; 32];
+/// let ciphertext = ancryptor::encrypt("hello_world_from_rust", &key);
+///
+/// let plaintext = ancryptor::decrypt_zeroizing(&ciphertext, &key).unwrap();
+///
+/// assert_eq!(plaintext.as_str(), "hello_world_from_rust");
+/// ```
+pub fn decrypt_zeroizing(ciphertext: &str, key: &[u8; 32]) -> Result<Zeroizing<String>, CryptoError> {
+    let sealed = crate::decode_bytes(ciphertext).map_err(|_| CryptoError::Malformed)?;
+
+    open_with_nonce(&sealed, key)
+}
+
+///
+/// Like [`encrypt`], for callers holding a password rather than a
+/// 32-byte key.
+///
+/// The key is derived from `password` with Argon2id (default parameters)
+/// and a random 16-byte salt, which is stored in front of the nonce:
+/// `salt || nonce || ciphertext+tag`, as standard base64. Derivation is
+/// deliberately slow, to make guessing passwords expensive.
+///
+/// ## Example
+/// ```
+/// let ciphertext = ancryptor::encrypt_with_password("hello", "correct horse");
+///
+/// assert_eq!(ancryptor::decrypt_with_password(&ciphertext, "correct horse").unwrap(), "hello");
+/// ```
+pub fn encrypt_with_password(plaintext: &str, password: &str) -> String {
+    let mut salt = [0; PASSWORD_SALT_LEN];
+    OsRng.fill_bytes(&mut salt);
+
+    let mut sealed = salt.to_vec();
+    seal_with_random_nonce(&mut sealed, plaintext, &password_key(password, &salt));
+
+    crate::encode_bytes(&sealed)
+}
+
+///
+/// Decrypts the output of [`encrypt_with_password`], deriving the key
+/// again from `password` and the stored salt.
+///
+/// A wrong password fails with [`CryptoError::AuthenticationFailed`];
+/// otherwise the errors are those of [`decrypt`].
+///
+pub fn decrypt_with_password(ciphertext: &str, password: &str) -> Result<String, CryptoError> {
+    let sealed = crate::decode_bytes(ciphertext).map_err(|_| CryptoError::Malformed)?;
+
+    if sealed.len() < PASSWORD_SALT_LEN {
+        return Err(CryptoError::InvalidLength);
+    }
+
+    let (salt, sealed) = sealed.split_at(PASSWORD_SALT_LEN);
+    open_with_nonce(sealed, &password_key(password, salt)).map(|plaintext| plaintext.as_str().to_owned())
+}
+
+///
+/// Appends a fresh nonce and the AES-256-GCM encryption of `plaintext`.
+///
+fn seal_with_random_nonce(output: &mut Vec<u8>, plaintext: &str, key: &[u8; 32]) {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = Aes256Gcm::new(key.into())
+        .encrypt(&nonce, plaintext.as_bytes())
+        .expect("AES-GCM encryption failed");
+
+    output.reserve(NONCE_LEN + ciphertext.len());
+    output.extend_from_slice(&nonce);
+    output.extend_from_slice(&ciphertext);
+}
+
+///
+/// Reverses [`seal_with_random_nonce`]. The decrypted bytes are wiped on
+/// every path, including when they turn out not to be UTF-8.
+///
+fn open_with_nonce(sealed: &[u8], key: &[u8; 32]) -> Result<Zeroizing<String>, CryptoError> {
+    if sealed.len() < NONCE_LEN + TAG_LEN {
+        return Err(CryptoError::InvalidLength);
+    }
+
+    let (nonce, ciphertext) = sealed.split_at(NONCE_LEN);
+    let mut plaintext = Zeroizing::new(
+        Aes256Gcm::new(key.into())
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| CryptoError::AuthenticationFailed)?,
+    );
+
+    // Checked before moving the bytes out, as `FromUtf8Error` would hand
+    // them back outside of the wrapper.
+    if core::str::from_utf8(&plaintext).is_err() {
+        return Err(CryptoError::Malformed);
+    }
+
+    let plaintext = String::from_utf8(core::mem::take(&mut *plaintext)).expect("checked to be UTF-8 above");
+    Ok(Zeroizing::new(plaintext))
+}
+
+fn password_key(password: &str, salt: &[u8]) -> Zeroizing<[u8; 32]> {
+    let mut key = Zeroizing::new([0; 32]);
+
+    Argon2::default()
+        .hash_password_into(password.as_bytes(), salt, key.as_mut())
+        .expect("a 16-byte salt and 32-byte output are valid Argon2 parameters");
+
+    key
+}
+
+///
+/// Derives a 256-bit subkey from `master` for one `purpose`, such as
+/// `"encryption"` or `"signing"`, using HKDF-SHA256 with the purpose as
+/// the `info` label.
+///
+/// Each purpose gets an independent key, so a single stored secret can
+/// serve several uses without one leaking into another. The same master
+/// and purpose always give the same subkey.
+///
+/// ## Example
+/// ```
+/// let master = [9u8; 32];
+///
+/// let encryption_key = ancryptor::derive_subkey(&master, "encryption");
+/// let signing_key = ancryptor::derive_subkey(&master, "signing");
+///
+/// assert_ne!(encryption_key, signing_key);
+/// ```
+pub fn derive_subkey(master: &[u8; 32], purpose: &str) -> [u8; 32] {
+    let mut subkey = [0; 32];
+
+    Hkdf::<Sha256>::new(None, master)
+        .expand(purpose.as_bytes(), &mut subkey)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+
+    subkey
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [42; 32];
+
+    #[test]
+    fn test_convergent_identical_inputs_yield_identical_outputs() {
+        let first = encrypt_convergent(b"same blob", &KEY);
+        let second = encrypt_convergent(b"same blob", &KEY);
+
+        assert_eq!(first, second);
+    }
+
+    #[test]
+    fn test_convergent_different_inputs_yield_different_outputs() {
+        let first = encrypt_convergent(b"first blob", &KEY);
+        let second = encrypt_convergent(b"other blob", &KEY);
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_convergent_round_trip() {
+        let ciphertext = encrypt_convergent(b"hello_world_from_rust", &KEY);
+
+        assert_eq!(decrypt_convergent(&ciphertext, &KEY).unwrap(), b"hello_world_from_rust");
+    }
+
+    #[test]
+    fn test_convergent_wrong_key_fails() {
+        let ciphertext = encrypt_convergent(b"hello_world_from_rust", &KEY);
+
+        assert_eq!(decrypt_convergent(&ciphertext, &[0; 32]), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_aad_round_trip_with_matching_aad() {
+        let nonce = [9; NONCE_LEN];
+        let ciphertext = encrypt_aes_gcm_aad(b"balance=100", &KEY, &nonce, b"record-7").unwrap();
+
+        assert_eq!(decrypt_aes_gcm_aad(&ciphertext, &KEY, &nonce, b"record-7").unwrap(), b"balance=100");
+    }
+
+    #[test]
+    fn test_aad_mismatch_fails_authentication() {
+        let nonce = [9; NONCE_LEN];
+        let ciphertext = encrypt_aes_gcm_aad(b"balance=100", &KEY, &nonce, b"record-7").unwrap();
+
+        assert_eq!(
+            decrypt_aes_gcm_aad(&ciphertext, &KEY, &nonce, b"record-8"),
+            Err(CryptoError::AuthenticationFailed),
+        );
+    }
+
+    #[test]
+    fn test_encrypt_round_trip() {
+        let ciphertext = encrypt("hello_world_from_rust", &KEY);
+
+        assert_eq!(decrypt(&ciphertext, &KEY), Ok("hello_world_from_rust".to_owned()));
+        assert_eq!(decrypt(&encrypt("", &KEY), &KEY), Ok("".to_owned()));
+    }
+
+    #[test]
+    fn test_decrypt_zeroizing_returns_wrapped_plaintext() {
+        let ciphertext = encrypt("hello_world_from_rust", &KEY);
+
+        let plaintext: Zeroizing<String> = decrypt_zeroizing(&ciphertext, &KEY).unwrap();
+
+        assert_eq!(plaintext.as_str(), "hello_world_from_rust");
+        assert_eq!(decrypt_zeroizing(&ciphertext, &[43; 32]), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_zeroizing_variants_return_wrapped_plaintext() {
+        let convergent: Zeroizing<Vec<u8>> = decrypt_convergent_zeroizing(&encrypt_convergent(b"blob", &KEY), &KEY).unwrap();
+        let nonce = [2; NONCE_LEN];
+        let ciphertext = encrypt_aes_gcm_aad(b"secret", &KEY, &nonce, b"record-7").unwrap();
+        let aad: Zeroizing<Vec<u8>> = decrypt_aes_gcm_aad_zeroizing(&ciphertext, &KEY, &nonce, b"record-7").unwrap();
+
+        assert_eq!(convergent.as_slice(), b"blob");
+        assert_eq!(aad.as_slice(), b"secret");
+        assert_eq!(
+            decrypt_aes_gcm_aad_zeroizing(&ciphertext, &KEY, &nonce, b"record-8"),
+            Err(CryptoError::AuthenticationFailed),
+        );
+    }
+
+    #[test]
+    fn test_encrypt_flipped_byte_fails_authentication() {
+        let mut sealed = crate::decode_bytes(&encrypt("hello_world_from_rust", &KEY)).unwrap();
+        sealed[NONCE_LEN + 3] ^= 0x01;
+
+        assert_eq!(decrypt(&crate::encode_bytes(&sealed), &KEY), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_decrypt_rejects_wrong_key_and_short_input() {
+        let ciphertext = encrypt("hello_world_from_rust", &KEY);
+
+        assert_eq!(decrypt(&ciphertext, &[43; 32]), Err(CryptoError::AuthenticationFailed));
+        assert_eq!(decrypt(&crate::encode_bytes(&[0; NONCE_LEN + TAG_LEN - 1]), &KEY), Err(CryptoError::InvalidLength));
+        assert_eq!(decrypt("not base64!", &KEY), Err(CryptoError::Malformed));
+    }
+
+    #[test]
+    fn test_password_round_trip_uses_fresh_salts() {
+        let first = encrypt_with_password("hello_world_from_rust", "correct horse");
+        let second = encrypt_with_password("hello_world_from_rust", "correct horse");
+
+        assert_ne!(first, second);
+        assert_ne!(first[..24], second[..24]);
+        assert_eq!(decrypt_with_password(&first, "correct horse"), Ok("hello_world_from_rust".to_owned()));
+        assert_eq!(decrypt_with_password(&second, "correct horse"), Ok("hello_world_from_rust".to_owned()));
+    }
+
+    #[test]
+    fn test_password_wrong_password_fails() {
+        let ciphertext = encrypt_with_password("hello_world_from_rust", "correct horse");
+
+        assert_eq!(decrypt_with_password(&ciphertext, "battery staple"), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_password_short_input_fails() {
+        assert_eq!(decrypt_with_password(&crate::encode_bytes(&[0; PASSWORD_SALT_LEN]), "pw"), Err(CryptoError::InvalidLength));
+    }
+
+    #[test]
+    fn test_derive_subkey_differs_per_purpose() {
+        let encryption = derive_subkey(&KEY, "encryption");
+        let signing = derive_subkey(&KEY, "signing");
+        let fingerprint = derive_subkey(&KEY, "fingerprint");
+
+        assert_ne!(encryption, signing);
+        assert_ne!(encryption, fingerprint);
+        assert_ne!(signing, fingerprint);
+        assert_ne!(encryption, KEY);
+    }
+
+    #[test]
+    fn test_derive_subkey_is_stable() {
+        assert_eq!(derive_subkey(&KEY, "encryption"), derive_subkey(&KEY, "encryption"));
+        assert_ne!(derive_subkey(&KEY, "encryption"), derive_subkey(&[0; 32], "encryption"));
+    }
+}
@@ -0,0 +1,152 @@
+use base64::{
+    Engine as _,
+    engine::general_purpose::URL_SAFE_NO_PAD as base64UrlSafeNoPadEngine,
+};
+use sha2::{Digest, Sha256};
+
+///
+/// A [`short_fingerprint`] length that keeps URLs short while making
+/// accidental collisions between assets very unlikely (48 bits).
+///
+pub const DEFAULT_FINGERPRINT_LEN: usize = 8;
+
+///
+/// Returns a stable, filesystem-safe content id for `data`: the SHA-256
+/// digest encoded as unpadded url-safe base64 (43 characters).
+///
+/// Identical inputs always produce identical ids, which makes it suitable
+/// for cache keys and content-addressed storage.
+///
+/// ## Example
+/// ```
+/// let id = ancryptor::canonical_id(b"hello");
+///
+/// assert_eq!(id, "LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ");
+/// ```
+pub fn canonical_id(data: &[u8]) -> String {
+    base64UrlSafeNoPadEngine.encode(Sha256::digest(data))
+}
+
+///
+/// Returns the first `len` characters of [`canonical_id`], e.g. to append
+/// to a static asset URL as a cache-busting `?v=...`.
+///
+/// The result is url-safe and changes whenever the content does. A `len`
+/// above 43 returns the whole id.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{short_fingerprint, DEFAULT_FINGERPRINT_LEN};
+///
+/// let fingerprint = short_fingerprint(b"hello", DEFAULT_FINGERPRINT_LEN);
+///
+/// assert_eq!(fingerprint, "LPJNul-w");
+/// ```
+pub fn short_fingerprint(data: &[u8], len: usize) -> String {
+    let mut fingerprint = canonical_id(data);
+    // The id is ASCII, so any length is a character boundary.
+    fingerprint.truncate(len);
+    fingerprint
+}
+
+///
+/// Returns a deterministic token of exactly `token_len` url-safe
+/// characters derived from `data`, e.g. for a fixed-width deduplication
+/// key column.
+///
+/// This is a hash, not an encoding: the data cannot be recovered from
+/// the token. Lengths above the 43 characters of a SHA-256 digest are
+/// filled by hashing again, chained on the previous digest.
+///
+/// ## Example
+/// ```
+/// let token = ancryptor::encode_fixed_from_hash(b"hello", 64);
+///
+/// assert_eq!(token.len(), 64);
+/// assert_eq!(token, ancryptor::encode_fixed_from_hash(b"hello", 64));
+/// ```
+pub fn encode_fixed_from_hash(data: &[u8], token_len: usize) -> String {
+    // 4 base64 characters per 3 bytes, rounded up to whole digests.
+    let bytes_needed = (token_len * 3).div_ceil(4);
+    let mut hashed = Vec::with_capacity(bytes_needed + 32);
+    let mut digest = Sha256::digest(data);
+
+    loop {
+        hashed.extend_from_slice(&digest);
+        if hashed.len() >= bytes_needed {
+            break;
+        }
+        digest = Sha256::new().chain_update(digest).chain_update(data).finalize();
+    }
+
+    let mut token = base64UrlSafeNoPadEngine.encode(hashed);
+    token.truncate(token_len);
+    token
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_canonical_id_is_stable() {
+        assert_eq!(canonical_id(b"same content"), canonical_id(b"same content"));
+    }
+
+    #[test]
+    fn test_canonical_id_differs_for_different_inputs() {
+        assert_ne!(canonical_id(b"first content"), canonical_id(b"other content"));
+    }
+
+    #[test]
+    fn test_canonical_id_is_filesystem_safe() {
+        let id = canonical_id(b"hello_world_from_rust");
+
+        assert_eq!(id.len(), 43);
+        assert!(id.chars().all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_'));
+    }
+
+    #[test]
+    fn test_short_fingerprint_is_stable() {
+        let first = short_fingerprint(b"body { color: red }", DEFAULT_FINGERPRINT_LEN);
+        let second = short_fingerprint(b"body { color: red }", DEFAULT_FINGERPRINT_LEN);
+
+        assert_eq!(first, second);
+        assert_eq!(first.len(), DEFAULT_FINGERPRINT_LEN);
+    }
+
+    #[test]
+    fn test_short_fingerprint_is_a_prefix_of_the_canonical_id() {
+        let id = canonical_id(b"app.js");
+
+        assert!(id.starts_with(&short_fingerprint(b"app.js", 12)));
+        assert_eq!(short_fingerprint(b"app.js", 100), id);
+        assert_eq!(short_fingerprint(b"app.js", 0), "");
+    }
+
+    #[test]
+    fn test_fixed_from_hash_has_exactly_token_len_characters() {
+        for token_len in [0, 1, 8, 42, 43, 44, 64, 100, 255] {
+            let token = encode_fixed_from_hash(b"dedup me", token_len);
+
+            assert_eq!(token.len(), token_len);
+            assert!(token.chars().all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_'));
+        }
+    }
+
+    #[test]
+    fn test_fixed_from_hash_is_deterministic() {
+        assert_eq!(encode_fixed_from_hash(b"dedup me", 80), encode_fixed_from_hash(b"dedup me", 80));
+        assert_ne!(encode_fixed_from_hash(b"dedup me", 80), encode_fixed_from_hash(b"dedup you", 80));
+    }
+
+    #[test]
+    fn test_fixed_from_hash_shorter_tokens_are_prefixes() {
+        let long = encode_fixed_from_hash(b"dedup me", 120);
+
+        assert!(long.starts_with(&encode_fixed_from_hash(b"dedup me", 50)));
+    }
+}
@@ -0,0 +1,484 @@
+// @See RFC 4648 (Base16, Base32 and Base64):
+//  - https://www.rfc-editor.org/rfc/rfc4648
+
+use base64::{
+    Engine as _,
+    engine::general_purpose::URL_SAFE as base64UrlSafeEngine,
+};
+use data_encoding::{BASE32, HEXLOWER_PERMISSIVE};
+
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use crate::error::DecodeError;
+
+///
+/// The text encodings supported by the crate.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EncodingKind {
+    /// Standard, padded base64 (`+` and `/`).
+    Base64,
+
+    /// URL and filename safe, padded base64 (`-` and `_`).
+    Base64UrlSafe,
+
+    /// RFC 4648 base32 with the uppercase alphabet and `=` padding.
+    Base32,
+
+    /// Lowercase hexadecimal.
+    Hex,
+}
+
+static BASE64_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+static BASE64_URL_SAFE_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789-_";
+static BASE32_ALPHABET: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ234567";
+static HEX_ALPHABET: &str = "0123456789abcdef";
+
+///
+/// Returns the characters used by `kind`, in value order, so the
+/// character at index `n` encodes the value `n`.
+///
+/// The padding character is not included, see [`pad_char_for`].
+///
+/// ## Example
+/// ```
+/// use ancryptor::{alphabet_for, EncodingKind};
+///
+/// assert_eq!(alphabet_for(EncodingKind::Hex), "0123456789abcdef");
+/// ```
+pub fn alphabet_for(kind: EncodingKind) -> &'static str {
+    match kind {
+        EncodingKind::Base64 => BASE64_ALPHABET,
+        EncodingKind::Base64UrlSafe => BASE64_URL_SAFE_ALPHABET,
+        EncodingKind::Base32 => BASE32_ALPHABET,
+        EncodingKind::Hex => HEX_ALPHABET,
+    }
+}
+
+///
+/// Returns the padding character used by `kind`, if it pads at all.
+///
+pub fn pad_char_for(kind: EncodingKind) -> Option<char> {
+    match kind {
+        EncodingKind::Base64 | EncodingKind::Base64UrlSafe | EncodingKind::Base32 => Some('='),
+        EncodingKind::Hex => None,
+    }
+}
+
+///
+/// Returns the length of the encoded output for `input_len` raw bytes.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{encoded_len, EncodingKind};
+///
+/// assert_eq!(encoded_len(5, EncodingKind::Base64), 8);
+/// assert_eq!(encoded_len(5, EncodingKind::Hex), 10);
+/// ```
+pub fn encoded_len(input_len: usize, kind: EncodingKind) -> usize {
+    match kind {
+        // Every started group of 3 bytes becomes 4 characters, padded.
+        EncodingKind::Base64 | EncodingKind::Base64UrlSafe => input_len.div_ceil(3) * 4,
+        // Every started group of 5 bytes becomes 8 characters, padded.
+        EncodingKind::Base32 => input_len.div_ceil(5) * 8,
+        EncodingKind::Hex => input_len * 2,
+    }
+}
+
+///
+/// Returns how many raw bytes can be encoded without the output growing
+/// past `output_budget` characters - the inverse of [`encoded_len`].
+///
+/// Useful for sizing values that are stored in a fixed-width column.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{max_input_bytes_for_output, EncodingKind};
+///
+/// // A VARCHAR(255) fits 189 bytes of base64...
+/// assert_eq!(max_input_bytes_for_output(255, EncodingKind::Base64), 189);
+/// // ...or 127 bytes of hex.
+/// assert_eq!(max_input_bytes_for_output(255, EncodingKind::Hex), 127);
+/// ```
+pub fn max_input_bytes_for_output(output_budget: usize, kind: EncodingKind) -> usize {
+    match kind {
+        EncodingKind::Base64 | EncodingKind::Base64UrlSafe => output_budget / 4 * 3,
+        EncodingKind::Base32 => output_budget / 8 * 5,
+        EncodingKind::Hex => output_budget / 2,
+    }
+}
+
+///
+/// Encodes `data` in the given format.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{encode_as, EncodingKind};
+///
+/// assert_eq!(encode_as(b"hi", EncodingKind::Hex), "6869");
+/// ```
+pub fn encode_as(data: &[u8], kind: EncodingKind) -> String {
+    match kind {
+        EncodingKind::Base64 => crate::encode_bytes(data),
+        EncodingKind::Base64UrlSafe => base64UrlSafeEngine.encode(data),
+        EncodingKind::Base32 => encode_base32(data),
+        EncodingKind::Hex => encode_hex(data),
+    }
+}
+
+///
+/// Decodes `from` according to the given format.
+///
+pub fn decode_as(from: &str, kind: EncodingKind) -> Result<Vec<u8>, DecodeError> {
+    match kind {
+        EncodingKind::Base64 => crate::decode_bytes(from),
+        EncodingKind::Base64UrlSafe => base64UrlSafeEngine.decode(from).map_err(DecodeError::InvalidBase64),
+        EncodingKind::Base32 => decode_base32(from),
+        EncodingKind::Hex => decode_hex(from),
+    }
+}
+
+///
+/// Encodes `data` as standard base64, switching to the url-safe alphabet
+/// only when the standard output would contain `+` or `/`.
+///
+/// Returns the encoded string and whether the url-safe alphabet was
+/// used; pass the flag to [`decode_adaptive`] to reverse it.
+///
+/// ## Example
+/// ```
+/// let (plain, url_safe) = ancryptor::encode_adaptive(b"hello");
+/// assert_eq!((plain.as_str(), url_safe), ("aGVsbG8=", false));
+///
+/// let (switched, url_safe) = ancryptor::encode_adaptive(&[0xFB, 0xFF]);
+/// assert_eq!((switched.as_str(), url_safe), ("-_8=", true));
+/// ```
+pub fn encode_adaptive(data: &[u8]) -> (String, bool) {
+    let standard = encode_as(data, EncodingKind::Base64);
+
+    if standard.contains(['+', '/']) {
+        return (encode_as(data, EncodingKind::Base64UrlSafe), true);
+    }
+
+    (standard, false)
+}
+
+///
+/// Decodes the output of [`encode_adaptive`], given the flag it returned.
+///
+pub fn decode_adaptive(from: &str, url_safe: bool) -> Result<Vec<u8>, DecodeError> {
+    let kind = if url_safe { EncodingKind::Base64UrlSafe } else { EncodingKind::Base64 };
+
+    decode_as(from, kind)
+}
+
+///
+/// Encodes `data` as RFC 4648 base32.
+///
+pub fn encode_base32(data: &[u8]) -> String {
+    BASE32.encode(data)
+}
+
+///
+/// Encodes `data` as RFC 4648 base32 using the lowercase alphabet, for
+/// systems that require lowercase identifiers.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::encode_base32_lower(b"hi"), "nbuq====");
+/// ```
+pub fn encode_base32_lower(data: &[u8]) -> String {
+    // The output is ASCII, so lowercasing cannot change its length.
+    encode_base32(data).to_ascii_lowercase()
+}
+
+///
+/// Decodes RFC 4648 base32, accepting both upper and lowercase letters.
+///
+/// Missing `=` padding is tolerated, since TOTP secrets and DNS labels
+/// usually leave it off.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::decode_base32("MZXW6YTBOI======").unwrap(), b"foobar");
+/// assert_eq!(ancryptor::decode_base32("mzxw6ytboi").unwrap(), b"foobar");
+/// ```
+pub fn decode_base32(from: &str) -> Result<Vec<u8>, DecodeError> {
+    let unpadded = from.trim_end_matches('=');
+
+    let mut padded = unpadded.to_ascii_uppercase();
+    padded.extend(core::iter::repeat_n('=', unpadded.len().next_multiple_of(8) - unpadded.len()));
+
+    BASE32.decode(padded.as_bytes()).map_err(DecodeError::InvalidBase32)
+}
+
+///
+/// Encodes `data` as lowercase hex with no separators.
+///
+pub fn encode_hex(data: &[u8]) -> String {
+    HEXLOWER_PERMISSIVE.encode(data)
+}
+
+///
+/// Decodes hex, accepting both upper and lowercase digits.
+///
+/// Odd-length input and non-hex characters fail with
+/// [`DecodeError::InvalidHex`], whose message gives the position.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::decode_hex("DEADbeef").unwrap(), [0xDE, 0xAD, 0xBE, 0xEF]);
+/// assert!(ancryptor::decode_hex("abc").is_err());
+/// ```
+pub fn decode_hex(from: &str) -> Result<Vec<u8>, DecodeError> {
+    HEXLOWER_PERMISSIVE.decode(from.as_bytes()).map_err(DecodeError::InvalidHex)
+}
+
+///
+/// Guesses which encoding `input` is in, or `None` if it decodes as
+/// none of them, e.g. to route data of unknown origin.
+///
+/// Many strings are valid in several encodings (`"cafe"` is hex and
+/// base64), so the most restrictive alphabet wins: hex first, then
+/// base32, standard base64 and finally url-safe base64. Surrounding
+/// whitespace is ignored and an empty input is `None`.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{detect_encoding, EncodingKind};
+///
+/// assert_eq!(detect_encoding("6869"), Some(EncodingKind::Hex));
+/// assert_eq!(detect_encoding("aGk="), Some(EncodingKind::Base64));
+/// assert_eq!(detect_encoding("not encoded!"), None);
+/// ```
+pub fn detect_encoding(input: &str) -> Option<EncodingKind> {
+    let input = input.trim();
+
+    if input.is_empty() {
+        return None;
+    }
+
+    [EncodingKind::Hex, EncodingKind::Base32, EncodingKind::Base64, EncodingKind::Base64UrlSafe]
+        .into_iter()
+        .find(|kind| decode_as(input, *kind).is_ok())
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL_KINDS: [EncodingKind; 4] = [
+        EncodingKind::Base64,
+        EncodingKind::Base64UrlSafe,
+        EncodingKind::Base32,
+        EncodingKind::Hex,
+    ];
+
+    #[test]
+    fn test_every_kind_round_trips() {
+        let data = [0xFB, 0xFF, 0x00, 0x10, 0x80];
+
+        for kind in ALL_KINDS {
+            let encoded = encode_as(&data, kind);
+            assert_eq!(decode_as(&encoded, kind).unwrap(), data, "{:?} did not round-trip", kind);
+        }
+    }
+
+    #[test]
+    fn test_url_safe_kind_avoids_plus_and_slash() {
+        assert_eq!(encode_as(&[0xFB, 0xFF], EncodingKind::Base64), "+/8=");
+        assert_eq!(encode_as(&[0xFB, 0xFF], EncodingKind::Base64UrlSafe), "-_8=");
+    }
+
+    #[test]
+    fn test_base64_alphabet_matches_rfc_4648() {
+        // RFC 4648, Table 1: The Base 64 Alphabet.
+        let rfc_alphabet: String = ('A'..='Z').chain('a'..='z').chain('0'..='9').chain(['+', '/']).collect();
+
+        assert_eq!(alphabet_for(EncodingKind::Base64), rfc_alphabet);
+        assert_eq!(pad_char_for(EncodingKind::Base64), Some('='));
+    }
+
+    #[test]
+    fn test_alphabet_sizes() {
+        assert_eq!(alphabet_for(EncodingKind::Base64UrlSafe).len(), 64);
+        assert_eq!(alphabet_for(EncodingKind::Base32).len(), 32);
+        assert_eq!(alphabet_for(EncodingKind::Hex).len(), 16);
+        assert_eq!(pad_char_for(EncodingKind::Hex), None);
+    }
+
+    #[test]
+    fn test_alphabet_covers_encoded_output() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        for kind in ALL_KINDS {
+            let alphabet = alphabet_for(kind);
+            let pad = pad_char_for(kind);
+
+            assert!(
+                encode_as(&data, kind).chars().all(|character| alphabet.contains(character) || Some(character) == pad),
+                "{:?} produced a character outside its alphabet", kind,
+            );
+        }
+    }
+
+    #[test]
+    fn test_encoded_len_matches_actual_output() {
+        for input_len in 0..64 {
+            let data = vec![0xA5; input_len];
+
+            for kind in ALL_KINDS {
+                assert_eq!(encoded_len(input_len, kind), encode_as(&data, kind).len(), "{:?} of {} bytes", kind, input_len);
+            }
+        }
+    }
+
+    #[test]
+    fn test_max_input_bytes_for_base64_budget() {
+        assert_eq!(max_input_bytes_for_output(0, EncodingKind::Base64), 0);
+        assert_eq!(max_input_bytes_for_output(3, EncodingKind::Base64), 0);
+        assert_eq!(max_input_bytes_for_output(4, EncodingKind::Base64), 3);
+        assert_eq!(max_input_bytes_for_output(255, EncodingKind::Base64), 189);
+        assert_eq!(max_input_bytes_for_output(256, EncodingKind::Base64), 192);
+    }
+
+    #[test]
+    fn test_max_input_bytes_for_hex_budget() {
+        assert_eq!(max_input_bytes_for_output(1, EncodingKind::Hex), 0);
+        assert_eq!(max_input_bytes_for_output(255, EncodingKind::Hex), 127);
+        assert_eq!(max_input_bytes_for_output(256, EncodingKind::Hex), 128);
+    }
+
+    #[test]
+    fn test_max_input_bytes_is_the_largest_that_fits() {
+        for budget in 0..200 {
+            for kind in ALL_KINDS {
+                let max_input = max_input_bytes_for_output(budget, kind);
+
+                assert!(encoded_len(max_input, kind) <= budget, "{:?} budget {}", kind, budget);
+                assert!(encoded_len(max_input + 1, kind) > budget, "{:?} budget {}", kind, budget);
+            }
+        }
+    }
+
+    #[test]
+    fn test_detect_encoding_recognizes_each_kind() {
+        assert_eq!(detect_encoding(&encode_hex(b"hello")), Some(EncodingKind::Hex));
+        assert_eq!(detect_encoding(&encode_base32(b"hello")), Some(EncodingKind::Base32));
+        assert_eq!(detect_encoding(&encode_as(&[0xFB, 0xFF], EncodingKind::Base64)), Some(EncodingKind::Base64));
+        assert_eq!(detect_encoding(&encode_as(&[0xFB, 0xFF], EncodingKind::Base64UrlSafe)), Some(EncodingKind::Base64UrlSafe));
+    }
+
+    #[test]
+    fn test_detect_encoding_prefers_the_most_restrictive_alphabet() {
+        assert_eq!(detect_encoding("cafe"), Some(EncodingKind::Hex));
+        assert_eq!(detect_encoding("NBUQ===="), Some(EncodingKind::Base32));
+        assert_eq!(detect_encoding(" aGk=\n"), Some(EncodingKind::Base64));
+    }
+
+    #[test]
+    fn test_detect_encoding_unknown() {
+        for input in ["", "   ", "not encoded!", "aGk", "-_8=+/"] {
+            assert_eq!(detect_encoding(input), None, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_adaptive_keeps_standard_when_url_compatible() {
+        let (encoded, url_safe) = encode_adaptive(b"hello_world");
+
+        assert!(!url_safe);
+        assert_eq!(encoded, encode_as(b"hello_world", EncodingKind::Base64));
+        assert_eq!(decode_adaptive(&encoded, url_safe).unwrap(), b"hello_world");
+    }
+
+    #[test]
+    fn test_adaptive_switches_to_url_safe_for_plus_and_slash() {
+        for data in [&[0xFB][..], &[0xFF, 0xFF], &[0x00, 0x0F, 0xBF]] {
+            let (encoded, url_safe) = encode_adaptive(data);
+
+            assert!(url_safe, "{:?} should need url-safe", data);
+            assert!(!encoded.contains(['+', '/']));
+            assert_eq!(decode_adaptive(&encoded, url_safe).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_adaptive_wrong_flag_fails() {
+        let (encoded, url_safe) = encode_adaptive(&[0xFB, 0xFF]);
+
+        assert!(decode_adaptive(&encoded, !url_safe).is_err());
+    }
+
+    #[test]
+    fn test_base32_lower_round_trip() {
+        let encoded = encode_base32_lower(b"hello_world");
+
+        assert_eq!(encoded, "nbswy3dpl53w64tmmq======");
+        assert_eq!(decode_base32(&encoded).unwrap(), b"hello_world");
+    }
+
+    #[test]
+    fn test_base32_decode_is_case_insensitive() {
+        assert_eq!(decode_base32("NBSWY3DP").unwrap(), b"hello");
+        assert_eq!(decode_base32("nbswy3dp").unwrap(), b"hello");
+        assert_eq!(decode_base32("NbSwY3dP").unwrap(), b"hello");
+    }
+
+    // RFC 4648, section 10.
+    const BASE32_TEST_VECTORS: [(&str, &str); 7] = [
+        ("", ""),
+        ("f", "MY======"),
+        ("fo", "MZXQ===="),
+        ("foo", "MZXW6==="),
+        ("foob", "MZXW6YQ="),
+        ("fooba", "MZXW6YTB"),
+        ("foobar", "MZXW6YTBOI======"),
+    ];
+
+    #[test]
+    fn test_base32_rfc_4648_vectors() {
+        for (plain, encoded) in BASE32_TEST_VECTORS {
+            assert_eq!(encode_base32(plain.as_bytes()), encoded);
+            assert_eq!(decode_base32(encoded).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_base32_decode_tolerates_missing_padding() {
+        for (plain, encoded) in BASE32_TEST_VECTORS {
+            assert_eq!(decode_base32(encoded.trim_end_matches('=')).unwrap(), plain.as_bytes());
+        }
+    }
+
+    #[test]
+    fn test_base32_decode_rejects_invalid_input() {
+        for invalid in ["MZXW6YTB0I", "MZX", "M", "MZXW6YT!"] {
+            assert!(matches!(decode_base32(invalid), Err(DecodeError::InvalidBase32(_))), "{:?}", invalid);
+        }
+    }
+
+    #[test]
+    fn test_hex_round_trip() {
+        assert_eq!(encode_hex(&[]), "");
+        assert_eq!(decode_hex("").unwrap(), b"");
+        assert_eq!(encode_hex(&[0xDE, 0xAD, 0xBE, 0xEF]), "deadbeef");
+        assert_eq!(decode_hex("deadbeef").unwrap(), [0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(decode_hex("DEADBEEF").unwrap(), [0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+
+    #[test]
+    fn test_hex_rejects_odd_length_and_non_hex_input() {
+        let odd_length = decode_hex("dea").unwrap_err();
+        let non_hex = decode_hex("deadbeeg").unwrap_err();
+
+        assert!(matches!(odd_length, DecodeError::InvalidHex(_)));
+        assert!(odd_length.to_string().starts_with("invalid hex: invalid length"), "{}", odd_length);
+        assert!(matches!(non_hex, DecodeError::InvalidHex(_)));
+        assert!(non_hex.to_string().contains("invalid symbol at 7"), "{}", non_hex);
+    }
+}
@@ -0,0 +1,82 @@
+use crate::error::DecodeError;
+
+///
+/// Returns the Shannon entropy of `data` in bits per byte, between `0.0`
+/// (a single repeated byte) and `8.0` (every byte value equally likely).
+///
+/// Encrypted or compressed data scores close to `8.0`, while text and
+/// other structured data score much lower, so a supposedly encrypted blob
+/// with low entropy is likely not encrypted at all. This is a heuristic:
+/// short inputs cannot reach `8.0` even when random.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::shannon_entropy(b"aaaa"), 0.0);
+/// assert_eq!(ancryptor::shannon_entropy(b"abab"), 1.0);
+/// ```
+pub fn shannon_entropy(data: &[u8]) -> f64 {
+    if data.is_empty() {
+        return 0.0;
+    }
+
+    let mut counts = [0usize; 256];
+    for byte in data {
+        counts[*byte as usize] += 1;
+    }
+
+    let len = data.len() as f64;
+    let entropy: f64 = counts.iter()
+        .filter(|count| **count > 0)
+        .map(|count| {
+            let probability = *count as f64 / len;
+            -probability * probability.log2()
+        })
+        .sum();
+
+    // Summing `-p * log2(p)` for a single symbol gives `-0.0`.
+    entropy.abs()
+}
+
+///
+/// Decodes base64 `s` and returns the [`shannon_entropy`] of the decoded
+/// bytes.
+///
+pub fn decode_and_measure_entropy(s: &str) -> Result<f64, DecodeError> {
+    Ok(shannon_entropy(&crate::decode_bytes(s)?))
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_entropy_of_random_bytes_is_near_eight() {
+        let random: Vec<u8> = (0..1024).flat_map(|_| crate::generate_key().unwrap()).collect();
+        let encoded = crate::encode_bytes(&random);
+
+        assert!(decode_and_measure_entropy(&encoded).unwrap() > 7.9);
+    }
+
+    #[test]
+    fn test_entropy_of_repetitive_text_is_low() {
+        let repetitive = "hello hello hello hello ".repeat(100);
+
+        assert!(shannon_entropy(repetitive.as_bytes()) < 3.0);
+    }
+
+    #[test]
+    fn test_entropy_of_every_byte_value_is_eight() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        assert_eq!(shannon_entropy(&data), 8.0);
+    }
+
+    #[test]
+    fn test_decode_and_measure_entropy() {
+        assert_eq!(decode_and_measure_entropy("AAAA").unwrap(), 0.0);
+        assert!(decode_and_measure_entropy("not base64!").is_err());
+    }
+}
@@ -0,0 +1,824 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::Path;
+
+use aes_gcm::{
+    aead::{rand_core::RngCore, Aead, AeadCore, KeyInit, OsRng, Payload},
+    Aes256Gcm,
+    Nonce,
+};
+
+use zeroize::Zeroizing;
+
+use crate::crypto::{NONCE_LEN, TAG_LEN};
+use crate::encoding::{encoded_len, EncodingKind};
+use crate::error::CryptoError;
+
+// Bumped whenever the layout below changes.
+const ENVELOPE_VERSION: u8 = 1;
+const HEADER_LEN: usize = 2;
+
+// Multi-recipient envelopes use their own version byte, so `open` never
+// mistakes one for a single-key envelope, and a third header byte holding
+// the number of recipients.
+const MULTI_ENVELOPE_VERSION: u8 = 2;
+const MULTI_HEADER_LEN: usize = 3;
+// Each wrapped key is a nonce followed by the encrypted 32-byte content key.
+const WRAPPED_KEY_LEN: usize = NONCE_LEN + 32 + TAG_LEN;
+
+// File envelopes are a header line followed by one line per encrypted
+// chunk. Each chunk nonce is a random per-file prefix, the chunk counter
+// and a flag marking the last chunk, so chunks cannot be reordered,
+// dropped or appended unnoticed.
+const FILE_ENVELOPE_VERSION: u8 = 3;
+const FILE_NONCE_PREFIX_LEN: usize = 7;
+const FILE_HEADER_LEN: usize = 2 + FILE_NONCE_PREFIX_LEN;
+const FILE_CHUNK_LEN: usize = 64 * 1024;
+
+///
+/// The AEAD algorithms an [`Envelope`] can be sealed with.
+///
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Algorithm {
+    /// AES-256 in Galois/Counter Mode, with a 96-bit nonce.
+    Aes256Gcm,
+}
+
+impl Algorithm {
+    ///
+    /// The id stored in the envelope header.
+    ///
+    pub(crate) fn id(self) -> u8 {
+        match self {
+            Algorithm::Aes256Gcm => 1,
+        }
+    }
+
+    pub(crate) fn from_id(id: u8) -> Option<Algorithm> {
+        match id {
+            1 => Some(Algorithm::Aes256Gcm),
+            _ => None,
+        }
+    }
+
+    ///
+    /// Bytes the algorithm adds around the ciphertext: nonce and tag.
+    ///
+    fn overhead(self) -> usize {
+        match self {
+            Algorithm::Aes256Gcm => NONCE_LEN + TAG_LEN,
+        }
+    }
+}
+
+///
+/// A self-describing encrypted payload, encoded as standard base64.
+///
+/// The decoded bytes are laid out as:
+///
+/// ```text
+/// -----------------------------------------------------------
+///  version (1) | algorithm (1) | nonce (12) | ciphertext+tag
+/// -----------------------------------------------------------
+/// ```
+///
+/// The two header bytes are authenticated together with the
+/// ciphertext, so they cannot be changed without `open` failing.
+///
+/// ## Example
+/// ```
+/// use ancryptor::Envelope;
+///
+/// let key = [3u8; 32];
+/// let sealed = Envelope::seal(b"hello_world_from_rust", &key);
+///
+/// assert_eq!(Envelope::open(&sealed, &key).unwrap(), b"hello_world_from_rust");
+/// ```
+pub struct Envelope;
+
+impl Envelope {
+    ///
+    /// Encrypts `plaintext` with AES-256-GCM under a fresh random nonce.
+    ///
+    pub fn seal(plaintext: &[u8], key: &[u8; 32]) -> String {
+        let header = [ENVELOPE_VERSION, Algorithm::Aes256Gcm.id()];
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+
+        let cipher = Aes256Gcm::new(key.into());
+        let ciphertext = cipher.encrypt(&nonce, Payload { msg: plaintext, aad: &header })
+            .expect("AES-GCM encryption failed");
+
+        let mut sealed = Vec::with_capacity(HEADER_LEN + NONCE_LEN + ciphertext.len());
+        sealed.extend_from_slice(&header);
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+
+        crate::encode_bytes(&sealed)
+    }
+
+    ///
+    /// Returns the exact length of the string [`Envelope::seal`] produces
+    /// for a plaintext of `plaintext_len` bytes, without encrypting
+    /// anything, e.g. to size an encrypted store.
+    ///
+    /// ## Example
+    /// ```
+    /// use ancryptor::{Algorithm, Envelope};
+    ///
+    /// let sealed = Envelope::seal(b"hello", &[3u8; 32]);
+    ///
+    /// assert_eq!(Envelope::sealed_size(5, Algorithm::Aes256Gcm), sealed.len());
+    /// ```
+    pub fn sealed_size(plaintext_len: usize, alg: Algorithm) -> usize {
+        encoded_len(HEADER_LEN + alg.overhead() + plaintext_len, EncodingKind::Base64)
+    }
+
+    ///
+    /// Decrypts an envelope produced by [`Envelope::seal`].
+    ///
+    /// Returns [`CryptoError::Malformed`] if `s` is not an envelope at all,
+    /// [`CryptoError::UnsupportedAlgorithm`] if it names an algorithm this
+    /// version does not know, and [`CryptoError::AuthenticationFailed`] if
+    /// it was tampered with or sealed under another key.
+    ///
+    pub fn open(s: &str, key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
+        let sealed = decode_envelope(s)?;
+        parse_header(&sealed)?;
+
+        let (header, rest) = sealed.split_at(HEADER_LEN);
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+        let cipher = Aes256Gcm::new(key.into());
+
+        cipher.decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: header })
+            .map_err(|_| CryptoError::AuthenticationFailed)
+    }
+
+    ///
+    /// Returns the algorithm an envelope was sealed with, read from its
+    /// header without decrypting anything, so a policy layer can refuse
+    /// envelopes before they reach [`Envelope::open`].
+    ///
+    /// The header is only authenticated by `open`: use this to reject
+    /// envelopes early, never as proof of how one was sealed.
+    ///
+    /// Returns [`CryptoError::UnsupportedAlgorithm`] for an algorithm id
+    /// this version does not know, and [`CryptoError::Malformed`] if `s`
+    /// is not an envelope.
+    ///
+    /// ## Example
+    /// ```
+    /// use ancryptor::{Algorithm, Envelope};
+    ///
+    /// let sealed = Envelope::seal(b"payload", &[3u8; 32]);
+    ///
+    /// assert_eq!(Envelope::algorithm(&sealed), Ok(Algorithm::Aes256Gcm));
+    /// ```
+    pub fn algorithm(s: &str) -> Result<Algorithm, CryptoError> {
+        parse_header(&decode_envelope(s)?)
+    }
+
+    ///
+    /// Checks that an envelope authenticates under `key`, without
+    /// handing back the plaintext.
+    ///
+    /// AEAD can only verify the tag by decrypting, so the plaintext is
+    /// still produced internally, then dropped. Returns `Ok(false)` for a
+    /// tampered envelope and an error only if `s` is not an envelope.
+    ///
+    pub fn verify(s: &str, key: &[u8; 32]) -> Result<bool, CryptoError> {
+        match Envelope::open(s, key) {
+            Ok(_) => Ok(true),
+            Err(CryptoError::AuthenticationFailed) => Ok(false),
+            Err(error) => Err(error),
+        }
+    }
+
+    ///
+    /// Seals `plaintext` like [`Envelope::seal`], then splits the envelope
+    /// into fragments of at most `max_chunk` characters, e.g. to fit one
+    /// per QR code.
+    ///
+    /// Each fragment is self-describing: `<index>/<total>:<data>`, with
+    /// 1-based indices, so they can be scanned in any order and put back
+    /// together by [`Envelope::open_chunked`].
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `max_chunk` is too small to hold a fragment header and at
+    /// least one character of data.
+    ///
+    /// ## Example
+    /// ```
+    /// use ancryptor::Envelope;
+    ///
+    /// let key = [3u8; 32];
+    /// let mut fragments = Envelope::seal_chunked(&[0x42; 500], &key, 100);
+    /// fragments.reverse();
+    ///
+    /// assert!(fragments.iter().all(|fragment| fragment.len() <= 100));
+    /// assert_eq!(Envelope::open_chunked(&fragments, &key).unwrap(), [0x42; 500]);
+    /// ```
+    pub fn seal_chunked(plaintext: &[u8], key: &[u8; 32], max_chunk: usize) -> Vec<String> {
+        let sealed = Envelope::seal(plaintext, key);
+        let capacity = fragment_capacity(sealed.len(), max_chunk)
+            .unwrap_or_else(|| panic!("max_chunk of {} cannot fit a fragment header", max_chunk));
+
+        let chunks: Vec<&[u8]> = sealed.as_bytes().chunks(capacity).collect();
+        let total = chunks.len();
+
+        chunks.iter().enumerate()
+            // The envelope is base64, so every chunk is valid UTF-8.
+            .map(|(index, chunk)| format!("{}/{}:{}", index + 1, total, std::str::from_utf8(chunk).unwrap()))
+            .collect()
+    }
+
+    ///
+    /// Reassembles the fragments produced by [`Envelope::seal_chunked`], in
+    /// any order, and opens the envelope.
+    ///
+    /// Returns [`CryptoError::Malformed`] if a fragment header cannot be
+    /// read, the fragments disagree on the total, or an index is missing
+    /// or appears twice.
+    ///
+    pub fn open_chunked(parts: &[String], key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
+        let mut fragments: Vec<Option<&str>> = Vec::new();
+
+        for part in parts {
+            let (index, total, data) = parse_fragment(part).ok_or(CryptoError::Malformed)?;
+
+            // Every fragment must agree on the total, and there must be
+            // exactly that many of them.
+            if total != parts.len() {
+                return Err(CryptoError::Malformed);
+            }
+            if fragments.is_empty() {
+                fragments = vec![None; total];
+            }
+
+            if fragments[index - 1].replace(data).is_some() {
+                return Err(CryptoError::Malformed);
+            }
+        }
+
+        let sealed: Option<String> = fragments.into_iter().collect();
+        match sealed {
+            Some(sealed) if !sealed.is_empty() => Envelope::open(&sealed, key),
+            _ => Err(CryptoError::Malformed),
+        }
+    }
+
+    ///
+    /// Encrypts `plaintext` once, for several recipients that each hold a
+    /// different key.
+    ///
+    /// A random content key encrypts the data; that key is then wrapped
+    /// (encrypted) under every recipient key, and all wrapped keys are
+    /// stored in the envelope:
+    ///
+    /// ```text
+    /// ----------------------------------------------------------------------------------
+    ///  version (1) | algorithm (1) | recipients (1) | wrapped keys (60 each) | nonce (12)
+    ///  | ciphertext+tag
+    /// ----------------------------------------------------------------------------------
+    /// ```
+    ///
+    /// Everything before the content nonce is authenticated along with
+    /// the ciphertext, so recipients cannot be added or removed unnoticed.
+    ///
+    /// ## Panics
+    ///
+    /// Panics if `recipient_keys` is empty or holds more than 255 keys.
+    ///
+    /// ## Example
+    /// ```
+    /// use ancryptor::Envelope;
+    ///
+    /// let (alice, bob) = ([1u8; 32], [2u8; 32]);
+    /// let sealed = Envelope::seal_multi(b"shared doc", &[alice, bob]);
+    ///
+    /// assert_eq!(Envelope::open_multi(&sealed, &bob).unwrap(), b"shared doc");
+    /// ```
+    pub fn seal_multi(plaintext: &[u8], recipient_keys: &[[u8; 32]]) -> String {
+        let recipients = u8::try_from(recipient_keys.len()).ok().filter(|recipients| *recipients > 0)
+            .expect("seal_multi needs between 1 and 255 recipient keys");
+
+        let header = [MULTI_ENVELOPE_VERSION, Algorithm::Aes256Gcm.id(), recipients];
+        let mut content_key = Zeroizing::new([0u8; 32]);
+        OsRng.fill_bytes(content_key.as_mut());
+
+        let mut sealed = Vec::with_capacity(MULTI_HEADER_LEN + recipient_keys.len() * WRAPPED_KEY_LEN);
+        sealed.extend_from_slice(&header);
+
+        for recipient_key in recipient_keys {
+            let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+            let wrapped_key = Aes256Gcm::new(recipient_key.into())
+                .encrypt(&nonce, Payload { msg: &content_key[..], aad: &header })
+                .expect("AES-GCM encryption failed");
+
+            sealed.extend_from_slice(&nonce);
+            sealed.extend_from_slice(&wrapped_key);
+        }
+
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = Aes256Gcm::new((&*content_key).into())
+            .encrypt(&nonce, Payload { msg: plaintext, aad: &sealed })
+            .expect("AES-GCM encryption failed");
+
+        sealed.extend_from_slice(&nonce);
+        sealed.extend_from_slice(&ciphertext);
+
+        crate::encode_bytes(&sealed)
+    }
+
+    ///
+    /// Decrypts an envelope produced by [`Envelope::seal_multi`] with one
+    /// of the recipient keys.
+    ///
+    /// Returns [`CryptoError::AuthenticationFailed`] if `my_key` is not one
+    /// of the recipients or the envelope was tampered with, and
+    /// [`CryptoError::Malformed`] if `s` is not a multi-recipient envelope.
+    ///
+    pub fn open_multi(s: &str, my_key: &[u8; 32]) -> Result<Vec<u8>, CryptoError> {
+        let sealed = crate::decode_bytes(s).map_err(|_| CryptoError::Malformed)?;
+
+        if sealed.len() < MULTI_HEADER_LEN || sealed[0] != MULTI_ENVELOPE_VERSION {
+            return Err(CryptoError::Malformed);
+        }
+        Algorithm::from_id(sealed[1]).ok_or(CryptoError::UnsupportedAlgorithm(sealed[1]))?;
+
+        let wrapped_keys_len = sealed[2] as usize * WRAPPED_KEY_LEN;
+        if sealed.len() < MULTI_HEADER_LEN + wrapped_keys_len + NONCE_LEN + TAG_LEN {
+            return Err(CryptoError::Malformed);
+        }
+
+        let (authenticated, rest) = sealed.split_at(MULTI_HEADER_LEN + wrapped_keys_len);
+        let (header, wrapped_keys) = authenticated.split_at(MULTI_HEADER_LEN);
+        let my_cipher = Aes256Gcm::new(my_key.into());
+
+        // Only the recipient's own wrapped key authenticates under their key.
+        let content_key = wrapped_keys.chunks(WRAPPED_KEY_LEN)
+            .find_map(|wrapped_key| {
+                let (nonce, wrapped_key) = wrapped_key.split_at(NONCE_LEN);
+                my_cipher.decrypt(Nonce::from_slice(nonce), Payload { msg: wrapped_key, aad: header }).ok()
+            })
+            .map(Zeroizing::new)
+            .ok_or(CryptoError::AuthenticationFailed)?;
+
+        let (nonce, ciphertext) = rest.split_at(NONCE_LEN);
+
+        Aes256Gcm::new_from_slice(&content_key)
+            .map_err(|_| CryptoError::Malformed)?
+            .decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad: authenticated })
+            .map_err(|_| CryptoError::AuthenticationFailed)
+    }
+
+    ///
+    /// Encrypts the file at `input_path` into a text envelope at
+    /// `output_path`, in one pass and without holding the whole file in
+    /// memory.
+    ///
+    /// The file is sealed in 64 KiB chunks, each encrypted with AES-256-GCM
+    /// and written as its own base64 line after a header line. Open the
+    /// result with [`Envelope::open_file`].
+    ///
+    /// ## Example
+    /// ```no_run
+    /// use std::path::Path;
+    /// use ancryptor::Envelope;
+    ///
+    /// let key = [3u8; 32];
+    /// Envelope::seal_file(Path::new("backup.tar"), Path::new("backup.tar.sealed"), &key).unwrap();
+    /// Envelope::open_file(Path::new("backup.tar.sealed"), Path::new("restored.tar"), &key).unwrap();
+    /// ```
+    pub fn seal_file(input_path: &Path, output_path: &Path, key: &[u8; 32]) -> io::Result<()> {
+        let reader = File::open(input_path)?;
+        let mut writer = BufWriter::new(File::create(output_path)?);
+
+        seal_stream(reader, &mut writer, key)?;
+        writer.flush()
+    }
+
+    ///
+    /// Decrypts a file envelope produced by [`Envelope::seal_file`] into
+    /// `output_path`, one chunk at a time.
+    ///
+    /// A tampered, truncated or extended envelope, or the wrong key, fails
+    /// with an [`io::ErrorKind::InvalidData`] error wrapping the
+    /// [`CryptoError`]. Chunks are written as soon as they authenticate,
+    /// so on failure `output_path` is removed rather than left holding a
+    /// partial plaintext.
+    ///
+    pub fn open_file(input_path: &Path, output_path: &Path, key: &[u8; 32]) -> io::Result<()> {
+        let reader = BufReader::new(File::open(input_path)?);
+        let mut writer = BufWriter::new(File::create(output_path)?);
+
+        let result = open_stream(reader, &mut writer, key).and_then(|_| writer.flush());
+        if result.is_err() {
+            drop(writer);
+            let _ = fs::remove_file(output_path);
+        }
+
+        result
+    }
+}
+
+fn seal_stream<R: Read, W: Write>(mut reader: R, writer: &mut W, key: &[u8; 32]) -> io::Result<()> {
+    let mut header = [0; FILE_HEADER_LEN];
+    header[0] = FILE_ENVELOPE_VERSION;
+    header[1] = Algorithm::Aes256Gcm.id();
+    OsRng.fill_bytes(&mut header[2..]);
+
+    writeln!(writer, "{}", crate::encode_bytes(&header))?;
+
+    let cipher = Aes256Gcm::new(key.into());
+    let mut chunk = Vec::with_capacity(FILE_CHUNK_LEN);
+
+    for counter in 0..=u32::MAX {
+        chunk.clear();
+        reader.by_ref().take(FILE_CHUNK_LEN as u64).read_to_end(&mut chunk)?;
+
+        // A short chunk ends the file; a file that is an exact multiple of
+        // the chunk length ends with an empty one.
+        let last = chunk.len() < FILE_CHUNK_LEN;
+        let nonce = file_chunk_nonce(&header, counter, last);
+        let ciphertext = cipher.encrypt(&nonce, Payload { msg: &chunk, aad: &header })
+            .expect("AES-GCM encryption failed");
+
+        writeln!(writer, "{}", crate::encode_bytes(&ciphertext))?;
+
+        if last {
+            return Ok(());
+        }
+    }
+
+    Err(io::Error::new(io::ErrorKind::InvalidInput, "file is too large to seal"))
+}
+
+fn open_stream<R: BufRead, W: Write>(reader: R, writer: &mut W, key: &[u8; 32]) -> io::Result<()> {
+    let invalid = |error: CryptoError| io::Error::new(io::ErrorKind::InvalidData, error);
+
+    let mut lines = reader.lines().peekable();
+    let header = lines.next().ok_or(invalid(CryptoError::Malformed))??;
+    let header = crate::decode_bytes(&header).map_err(|_| invalid(CryptoError::Malformed))?;
+
+    if header.len() != FILE_HEADER_LEN || header[0] != FILE_ENVELOPE_VERSION {
+        return Err(invalid(CryptoError::Malformed));
+    }
+    Algorithm::from_id(header[1]).ok_or(invalid(CryptoError::UnsupportedAlgorithm(header[1])))?;
+
+    let cipher = Aes256Gcm::new(key.into());
+    let mut counter: u32 = 0;
+
+    while let Some(line) = lines.next() {
+        let ciphertext = crate::decode_bytes(&line?).map_err(|_| invalid(CryptoError::Malformed))?;
+        let last = lines.peek().is_none();
+
+        let nonce = file_chunk_nonce(&header, counter, last);
+        let chunk = cipher.decrypt(&nonce, Payload { msg: &ciphertext, aad: &header })
+            .map_err(|_| invalid(CryptoError::AuthenticationFailed))?;
+        writer.write_all(&chunk)?;
+
+        if last {
+            return Ok(());
+        }
+        counter = counter.checked_add(1).ok_or(invalid(CryptoError::Malformed))?;
+    }
+
+    // A header without any chunk has lost at least its last one.
+    Err(invalid(CryptoError::AuthenticationFailed))
+}
+
+///
+/// Nonce of chunk `counter`: the file's random prefix, the counter as
+/// four big-endian bytes, then 1 for the last chunk and 0 otherwise.
+///
+fn file_chunk_nonce(header: &[u8], counter: u32, last: bool) -> Nonce<<Aes256Gcm as AeadCore>::NonceSize> {
+    let mut nonce = [0; NONCE_LEN];
+    nonce[..FILE_NONCE_PREFIX_LEN].copy_from_slice(&header[2..]);
+    nonce[FILE_NONCE_PREFIX_LEN..NONCE_LEN - 1].copy_from_slice(&counter.to_be_bytes());
+    nonce[NONCE_LEN - 1] = last as u8;
+
+    nonce.into()
+}
+
+fn decode_envelope(s: &str) -> Result<Vec<u8>, CryptoError> {
+    let sealed = crate::decode_bytes(s).map_err(|_| CryptoError::Malformed)?;
+
+    if sealed.len() < HEADER_LEN + NONCE_LEN + TAG_LEN {
+        return Err(CryptoError::Malformed);
+    }
+
+    Ok(sealed)
+}
+
+///
+/// Checks the version byte and returns the algorithm named by the header.
+///
+fn parse_header(sealed: &[u8]) -> Result<Algorithm, CryptoError> {
+    if sealed[0] != ENVELOPE_VERSION {
+        return Err(CryptoError::Malformed);
+    }
+
+    Algorithm::from_id(sealed[1]).ok_or(CryptoError::UnsupportedAlgorithm(sealed[1]))
+}
+
+///
+/// Returns how many envelope characters fit in each fragment once the
+/// `<index>/<total>:` header is accounted for, or `None` if none do.
+///
+fn fragment_capacity(sealed_len: usize, max_chunk: usize) -> Option<usize> {
+    // The header grows with the number of digits in the total, which in
+    // turn depends on the capacity, so try each digit count in turn.
+    for digits in 1..=usize::MAX.to_string().len() {
+        let header_len = 2 * digits + 2;
+        let capacity = max_chunk.checked_sub(header_len).filter(|capacity| *capacity > 0)?;
+
+        if sealed_len.div_ceil(capacity).to_string().len() <= digits {
+            return Some(capacity);
+        }
+    }
+
+    None
+}
+
+fn parse_fragment(fragment: &str) -> Option<(usize, usize, &str)> {
+    let (header, data) = fragment.trim().split_once(':')?;
+    let (index, total) = header.split_once('/')?;
+    let index: usize = index.parse().ok()?;
+    let total: usize = total.parse().ok()?;
+
+    if index == 0 || index > total {
+        return None;
+    }
+
+    Some((index, total, data))
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: [u8; 32] = [11; 32];
+
+    fn tamper(sealed: &str, index: usize) -> String {
+        let mut bytes = crate::decode_bytes(sealed).unwrap();
+        bytes[index] ^= 0x01;
+        crate::encode_bytes(&bytes)
+    }
+
+    #[test]
+    fn test_envelope_round_trip() {
+        let sealed = Envelope::seal(b"hello_world_from_rust", &KEY);
+
+        assert_eq!(Envelope::open(&sealed, &KEY).unwrap(), b"hello_world_from_rust");
+    }
+
+    #[test]
+    fn test_envelope_uses_fresh_nonces() {
+        assert_ne!(Envelope::seal(b"same", &KEY), Envelope::seal(b"same", &KEY));
+    }
+
+    #[test]
+    fn test_envelope_verify_intact() {
+        let sealed = Envelope::seal(b"archive contents", &KEY);
+
+        assert_eq!(Envelope::verify(&sealed, &KEY), Ok(true));
+    }
+
+    #[test]
+    fn test_envelope_verify_tampered_ciphertext_is_false() {
+        let sealed = Envelope::seal(b"archive contents", &KEY);
+        let tampered = tamper(&sealed, HEADER_LEN + NONCE_LEN);
+
+        assert_eq!(Envelope::verify(&tampered, &KEY), Ok(false));
+        assert_eq!(Envelope::open(&tampered, &KEY), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_envelope_verify_wrong_key_is_false() {
+        let sealed = Envelope::seal(b"archive contents", &KEY);
+
+        assert_eq!(Envelope::verify(&sealed, &[0; 32]), Ok(false));
+    }
+
+    #[test]
+    fn test_envelope_verify_not_an_envelope_is_an_error() {
+        assert_eq!(Envelope::verify("dfoiuerw892", &KEY), Err(CryptoError::Malformed));
+        assert_eq!(Envelope::verify("aGk=", &KEY), Err(CryptoError::Malformed));
+    }
+
+    #[test]
+    fn test_chunked_reassembles_out_of_order_fragments() {
+        let plaintext: Vec<u8> = (0..=255).cycle().take(2_000).collect();
+        let mut fragments = Envelope::seal_chunked(&plaintext, &KEY, 120);
+
+        assert!(fragments.len() > 1);
+        assert!(fragments.iter().all(|fragment| fragment.len() <= 120));
+
+        fragments.swap(0, 3);
+        fragments.reverse();
+
+        assert_eq!(Envelope::open_chunked(&fragments, &KEY).unwrap(), plaintext);
+    }
+
+    #[test]
+    fn test_chunked_headers_are_numbered() {
+        let fragments = Envelope::seal_chunked(&[7; 200], &KEY, 64);
+        let total = fragments.len();
+
+        for (index, fragment) in fragments.iter().enumerate() {
+            assert!(fragment.starts_with(&format!("{}/{}:", index + 1, total)), "{}", fragment);
+        }
+    }
+
+    #[test]
+    fn test_chunked_missing_fragment_fails() {
+        let mut fragments = Envelope::seal_chunked(&[7; 200], &KEY, 64);
+        fragments.remove(1);
+
+        assert_eq!(Envelope::open_chunked(&fragments, &KEY), Err(CryptoError::Malformed));
+    }
+
+    #[test]
+    fn test_chunked_duplicate_fragment_fails() {
+        let mut fragments = Envelope::seal_chunked(&[7; 200], &KEY, 64);
+        let last = fragments.len() - 1;
+        fragments[last] = fragments[0].clone();
+
+        assert_eq!(Envelope::open_chunked(&fragments, &KEY), Err(CryptoError::Malformed));
+    }
+
+    #[test]
+    fn test_chunked_rejects_bad_headers() {
+        for fragment in ["", "1/1", "0/1:aGk=", "2/1:aGk=", "x/1:aGk="] {
+            assert_eq!(Envelope::open_chunked(&[fragment.to_owned()], &KEY), Err(CryptoError::Malformed));
+        }
+        assert_eq!(Envelope::open_chunked(&[], &KEY), Err(CryptoError::Malformed));
+    }
+
+    #[test]
+    fn test_fragment_capacity_accounts_for_header() {
+        assert_eq!(fragment_capacity(10, 4), None);
+        assert_eq!(fragment_capacity(10, 9), Some(5));
+        // 1000 characters over 10-character fragments needs a 3-digit total.
+        assert_eq!(fragment_capacity(1_000, 10), Some(2));
+        assert_eq!(fragment_capacity(1_000, 20), Some(14));
+    }
+
+    #[test]
+    fn test_algorithm_of_sealed_envelope() {
+        let sealed = Envelope::seal(b"archive contents", &KEY);
+
+        assert_eq!(Envelope::algorithm(&sealed), Ok(Algorithm::Aes256Gcm));
+    }
+
+    #[test]
+    fn test_algorithm_unknown_id_is_rejected() {
+        let mut bytes = crate::decode_bytes(&Envelope::seal(b"archive contents", &KEY)).unwrap();
+        bytes[1] = 0x7F;
+        let unknown = crate::encode_bytes(&bytes);
+
+        assert_eq!(Envelope::algorithm(&unknown), Err(CryptoError::UnsupportedAlgorithm(0x7F)));
+        assert_eq!(Envelope::open(&unknown, &KEY), Err(CryptoError::UnsupportedAlgorithm(0x7F)));
+    }
+
+    #[test]
+    fn test_algorithm_of_non_envelope_is_malformed() {
+        assert_eq!(Envelope::algorithm("aGk="), Err(CryptoError::Malformed));
+        assert_eq!(Envelope::algorithm("dfoiuerw892"), Err(CryptoError::Malformed));
+    }
+
+    #[test]
+    fn test_multi_three_recipients_only_two_can_open() {
+        let (alice, bob, carol) = ([1; 32], [2; 32], [3; 32]);
+        let sealed = Envelope::seal_multi(b"shared document", &[alice, bob]);
+
+        assert_eq!(Envelope::open_multi(&sealed, &alice).unwrap(), b"shared document");
+        assert_eq!(Envelope::open_multi(&sealed, &bob).unwrap(), b"shared document");
+        assert_eq!(Envelope::open_multi(&sealed, &carol), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_multi_tampered_recipient_list_fails() {
+        let (alice, bob) = ([1; 32], [2; 32]);
+        let sealed = Envelope::seal_multi(b"shared document", &[alice, bob]);
+
+        // Flip a byte in bob's wrapped key: alice can still unwrap hers, but
+        // the content tag covers every wrapped key.
+        let tampered = tamper(&sealed, MULTI_HEADER_LEN + WRAPPED_KEY_LEN + NONCE_LEN);
+
+        assert_eq!(Envelope::open_multi(&tampered, &alice), Err(CryptoError::AuthenticationFailed));
+    }
+
+    #[test]
+    fn test_multi_and_single_envelopes_do_not_mix() {
+        let single = Envelope::seal(b"data", &KEY);
+        let multi = Envelope::seal_multi(b"data", &[KEY]);
+
+        assert_eq!(Envelope::open_multi(&single, &KEY), Err(CryptoError::Malformed));
+        assert_eq!(Envelope::open(&multi, &KEY), Err(CryptoError::Malformed));
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_multi_without_recipients_panics() {
+        Envelope::seal_multi(b"data", &[]);
+    }
+
+    fn seal_and_open_file(plaintext: &[u8]) -> Vec<u8> {
+        let dir = tempfile::tempdir().unwrap();
+        let (input, sealed, output) = (dir.path().join("input"), dir.path().join("sealed"), dir.path().join("output"));
+        fs::write(&input, plaintext).unwrap();
+
+        Envelope::seal_file(&input, &sealed, &KEY).unwrap();
+        Envelope::open_file(&sealed, &output, &KEY).unwrap();
+
+        fs::read(output).unwrap()
+    }
+
+    fn sealed_file_lines(plaintext: &[u8]) -> Vec<String> {
+        let mut sealed = Vec::new();
+        seal_stream(plaintext, &mut sealed, &KEY).unwrap();
+
+        String::from_utf8(sealed).unwrap().lines().map(str::to_owned).collect()
+    }
+
+    fn open_file_lines(lines: &[String]) -> io::Result<Vec<u8>> {
+        let mut opened = Vec::new();
+        open_stream(lines.join("\n").as_bytes(), &mut opened, &KEY)?;
+
+        Ok(opened)
+    }
+
+    #[test]
+    fn test_file_round_trip_multi_megabyte() {
+        let plaintext: Vec<u8> = (0..3 * 1024 * 1024 + 17).map(|index: u32| (index * 31 % 251) as u8).collect();
+
+        assert_eq!(seal_and_open_file(&plaintext), plaintext);
+    }
+
+    #[test]
+    fn test_file_round_trip_chunk_boundaries() {
+        for len in [0, 1, FILE_CHUNK_LEN - 1, FILE_CHUNK_LEN, 2 * FILE_CHUNK_LEN] {
+            let plaintext = vec![0x5A; len];
+
+            assert_eq!(seal_and_open_file(&plaintext), plaintext, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_file_tampered_chunk_fails_and_removes_output() {
+        let dir = tempfile::tempdir().unwrap();
+        let (input, sealed, output) = (dir.path().join("input"), dir.path().join("sealed"), dir.path().join("output"));
+        fs::write(&input, vec![0x42; 3 * FILE_CHUNK_LEN]).unwrap();
+        Envelope::seal_file(&input, &sealed, &KEY).unwrap();
+
+        let mut lines: Vec<String> = fs::read_to_string(&sealed).unwrap().lines().map(str::to_owned).collect();
+        lines[2] = tamper(&lines[2], 100);
+        fs::write(&sealed, lines.join("\n")).unwrap();
+
+        let error = Envelope::open_file(&sealed, &output, &KEY).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert_eq!(error.into_inner().unwrap().downcast_ref(), Some(&CryptoError::AuthenticationFailed));
+        assert!(!output.exists());
+    }
+
+    #[test]
+    fn test_file_dropped_reordered_or_appended_chunks_fail() {
+        let lines = sealed_file_lines(&[0x42; 3 * FILE_CHUNK_LEN]);
+
+        let truncated = lines[..lines.len() - 1].to_vec();
+        let mut reordered = lines.clone();
+        reordered.swap(1, 2);
+        let mut extended = lines.clone();
+        extended.push(lines[1].clone());
+
+        for broken in [truncated, reordered, extended, lines[..1].to_vec()] {
+            assert_eq!(open_file_lines(&broken).unwrap_err().kind(), io::ErrorKind::InvalidData);
+        }
+        assert_eq!(open_file_lines(&lines).unwrap(), [0x42; 3 * FILE_CHUNK_LEN]);
+    }
+
+    #[test]
+    fn test_file_wrong_key_and_non_envelope_fail() {
+        let lines = sealed_file_lines(b"backup");
+
+        let mut opened = Vec::new();
+        assert!(open_stream(lines.join("\n").as_bytes(), &mut opened, &[12; 32]).is_err());
+        assert!(open_file_lines(&[Envelope::seal(b"backup", &KEY)]).is_err());
+        assert!(open_file_lines(&[]).is_err());
+    }
+
+    #[test]
+    fn test_sealed_size_matches_seal_output() {
+        for plaintext_len in [0, 1, 2, 3, 15, 16, 17, 100, 1_000] {
+            let sealed = Envelope::seal(&vec![0x5A; plaintext_len], &KEY);
+
+            assert_eq!(Envelope::sealed_size(plaintext_len, Algorithm::Aes256Gcm), sealed.len(), "{} bytes", plaintext_len);
+        }
+    }
+}
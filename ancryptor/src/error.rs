@@ -0,0 +1,260 @@
+use core::fmt;
+
+use alloc::string::{FromUtf8Error, String};
+
+#[cfg(feature = "std")]
+use std::error::Error;
+#[cfg(feature = "std")]
+use std::io;
+
+///
+/// Errors returned when an encoded string cannot be decoded.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The input is not valid base64.
+    InvalidBase64(base64::DecodeError),
+
+    /// The input is not valid base32.
+    InvalidBase32(data_encoding::DecodeError),
+
+    /// The input is not valid hex.
+    InvalidHex(data_encoding::DecodeError),
+
+    /// The decoded bytes are not valid UTF-8.
+    InvalidUtf8(FromUtf8Error),
+
+    /// The decoded bytes are not valid gzip data. Holds the message of the
+    /// original `io::Error`, which is neither `Clone` nor `Eq`.
+    InvalidGzip(String),
+
+    /// The input is not valid Base58. Holds the message of the original
+    /// error, so this variant does not depend on the `base58` feature.
+    InvalidBase58(String),
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DecodeError::InvalidBase64(error) => write!(f, "invalid base64: {}", error),
+            DecodeError::InvalidBase32(error) => write!(f, "invalid base32: {}", error),
+            DecodeError::InvalidHex(error) => write!(f, "invalid hex: {}", error),
+            DecodeError::InvalidUtf8(error) => write!(f, "invalid utf-8: {}", error),
+            DecodeError::InvalidGzip(message) => write!(f, "invalid gzip: {}", message),
+            DecodeError::InvalidBase58(message) => write!(f, "invalid base58: {}", message),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for DecodeError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            DecodeError::InvalidBase64(error) => Some(error),
+            DecodeError::InvalidBase32(error) => Some(error),
+            DecodeError::InvalidHex(error) => Some(error),
+            DecodeError::InvalidUtf8(error) => Some(error),
+            DecodeError::InvalidGzip(_) => None,
+            DecodeError::InvalidBase58(_) => None,
+        }
+    }
+}
+
+///
+/// Errors returned when a string cannot be encoded.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EncodeError {
+    /// The encoded output would be longer than `usize::MAX` bytes.
+    OutputTooLong,
+}
+
+impl fmt::Display for EncodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EncodeError::OutputTooLong => write!(f, "encoded output would be too long"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for EncodeError {}
+
+///
+/// Errors returned when building a [`crate::CustomCodec`].
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CodecError {
+    /// The alphabet is not 64 unique printable ASCII characters, or it
+    /// contains the `=` padding character.
+    InvalidAlphabet,
+}
+
+impl fmt::Display for CodecError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CodecError::InvalidAlphabet => write!(f, "alphabet must be 64 unique printable ASCII characters"),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CodecError {}
+
+///
+/// Errors returned by the encryption functions.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CryptoError {
+    /// The cipher refused to encrypt the input, e.g. because it is too long.
+    EncryptionFailed,
+
+    /// The ciphertext is too short to contain a nonce and an authentication tag.
+    InvalidLength,
+
+    /// The ciphertext was tampered with or was encrypted under a different key.
+    AuthenticationFailed,
+
+    /// The input is not in the expected format, e.g. not an envelope at all.
+    Malformed,
+
+    /// The operating system could not provide random bytes.
+    EntropyUnavailable,
+
+    /// An envelope names an algorithm this version does not support.
+    /// Holds the algorithm id from the header.
+    UnsupportedAlgorithm(u8),
+}
+
+impl fmt::Display for CryptoError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CryptoError::EncryptionFailed => write!(f, "encryption failed"),
+            CryptoError::InvalidLength => write!(f, "ciphertext is too short"),
+            CryptoError::AuthenticationFailed => write!(f, "ciphertext failed authentication"),
+            CryptoError::Malformed => write!(f, "malformed ciphertext"),
+            CryptoError::EntropyUnavailable => write!(f, "the system random number generator is unavailable"),
+            CryptoError::UnsupportedAlgorithm(id) => write!(f, "unsupported algorithm id {}", id),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for CryptoError {}
+
+///
+/// Crate-level error for operations that can fail for more than
+/// one reason.
+///
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AncryptorError {
+    /// The payload could not be decoded.
+    Decode(DecodeError),
+
+    /// A required query parameter is absent. Holds the parameter name.
+    MissingParam(String),
+
+    /// A framed payload has a length prefix that does not match its contents.
+    MalformedFrame,
+
+    /// An argument is outside the range the function accepts.
+    InvalidArgument(String),
+
+    /// A signed token is not in the `payload.signature` format or is not
+    /// base64url.
+    MalformedToken,
+
+    /// A signed token's signature does not match its payload.
+    InvalidSignature,
+
+    /// An expiring token is past its expiry time.
+    Expired,
+
+    /// A checksum embedded in the input does not match its contents.
+    ChecksumMismatch,
+
+    /// Reading or writing failed. Holds the kind and message of the
+    /// original `io::Error`, which is neither `Clone` nor `Eq`.
+    #[cfg(feature = "std")]
+    Io(io::ErrorKind, String),
+
+    /// Encrypting or decrypting failed.
+    Crypto(CryptoError),
+}
+
+impl fmt::Display for AncryptorError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AncryptorError::Decode(error) => write!(f, "{}", error),
+            AncryptorError::MissingParam(name) => write!(f, "missing query parameter '{}'", name),
+            AncryptorError::MalformedFrame => write!(f, "malformed frame"),
+            AncryptorError::InvalidArgument(reason) => write!(f, "invalid argument: {}", reason),
+            AncryptorError::MalformedToken => write!(f, "malformed token"),
+            AncryptorError::InvalidSignature => write!(f, "token signature does not match"),
+            AncryptorError::Expired => write!(f, "token has expired"),
+            AncryptorError::ChecksumMismatch => write!(f, "checksum mismatch"),
+            #[cfg(feature = "std")]
+            AncryptorError::Io(_, message) => write!(f, "i/o error: {}", message),
+            AncryptorError::Crypto(error) => write!(f, "{}", error),
+        }
+    }
+}
+
+#[cfg(feature = "std")]
+impl Error for AncryptorError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            AncryptorError::Decode(error) => Some(error),
+            AncryptorError::Crypto(error) => Some(error),
+            _ => None,
+        }
+    }
+}
+
+impl From<DecodeError> for AncryptorError {
+    fn from(error: DecodeError) -> Self {
+        AncryptorError::Decode(error)
+    }
+}
+
+impl From<base64::DecodeError> for AncryptorError {
+    fn from(error: base64::DecodeError) -> Self {
+        AncryptorError::Decode(DecodeError::InvalidBase64(error))
+    }
+}
+
+impl From<FromUtf8Error> for AncryptorError {
+    fn from(error: FromUtf8Error) -> Self {
+        AncryptorError::Decode(DecodeError::InvalidUtf8(error))
+    }
+}
+
+impl From<CryptoError> for AncryptorError {
+    fn from(error: CryptoError) -> Self {
+        AncryptorError::Crypto(error)
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<io::Error> for AncryptorError {
+    fn from(error: io::Error) -> Self {
+        AncryptorError::Io(error.kind(), error.to_string())
+    }
+}
+
+#[cfg(feature = "std")]
+impl From<AncryptorError> for io::Error {
+    ///
+    /// Keeps the kind of errors that came from `io::Error`; everything
+    /// else is either bad input data or a bad argument.
+    ///
+    fn from(error: AncryptorError) -> Self {
+        let kind = match &error {
+            AncryptorError::Io(kind, message) => return io::Error::new(*kind, message.to_owned()),
+            AncryptorError::MissingParam(_) | AncryptorError::InvalidArgument(_) => io::ErrorKind::InvalidInput,
+            _ => io::ErrorKind::InvalidData,
+        };
+
+        io::Error::new(kind, error)
+    }
+}
@@ -0,0 +1,121 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::encoding::{decode_as, EncodingKind};
+use crate::error::AncryptorError;
+
+///
+/// File extension, without the dot, of files holding `kind` text.
+///
+fn file_extension_for(kind: EncodingKind) -> &'static str {
+    match kind {
+        EncodingKind::Base64 | EncodingKind::Base64UrlSafe => "b64",
+        EncodingKind::Base32 => "b32",
+        EncodingKind::Hex => "hex",
+    }
+}
+
+///
+/// Checks that every encoded file under `dir`, subdirectories included,
+/// decodes cleanly as `kind`, e.g. to gate a CI build on its bundled
+/// assets.
+///
+/// Files are picked by extension: `.b64` for both base64 kinds, `.b32`
+/// and `.hex`. Trailing whitespace, such as a final newline, is ignored.
+/// Every file is checked even after a failure, and the results are
+/// sorted by path. A directory that cannot be read is reported as a
+/// failed entry of its own.
+///
+/// ## Example
+/// ```no_run
+/// use std::path::Path;
+/// use ancryptor::{validate_dir, EncodingKind};
+///
+/// for (path, result) in validate_dir(Path::new("assets"), EncodingKind::Base64) {
+///     if let Err(error) = result {
+///         eprintln!("{}: {}", path.display(), error);
+///     }
+/// }
+/// ```
+pub fn validate_dir(dir: &Path, kind: EncodingKind) -> Vec<(PathBuf, Result<(), AncryptorError>)> {
+    let mut results = Vec::new();
+    validate_dir_into(dir, kind, &mut results);
+
+    results.sort_by(|(left, _), (right, _)| left.cmp(right));
+    results
+}
+
+fn validate_dir_into(dir: &Path, kind: EncodingKind, results: &mut Vec<(PathBuf, Result<(), AncryptorError>)>) {
+    let entries = match fs::read_dir(dir) {
+        Ok(entries) => entries,
+        Err(error) => return results.push((dir.to_path_buf(), Err(error.into()))),
+    };
+
+    for entry in entries {
+        let path = match entry {
+            Ok(entry) => entry.path(),
+            Err(error) => {
+                results.push((dir.to_path_buf(), Err(error.into())));
+                continue;
+            }
+        };
+
+        if path.is_dir() {
+            validate_dir_into(&path, kind, results);
+        } else if path.extension().is_some_and(|extension| extension == file_extension_for(kind)) {
+            let result = validate_file(&path, kind);
+            results.push((path, result));
+        }
+    }
+}
+
+fn validate_file(path: &Path, kind: EncodingKind) -> Result<(), AncryptorError> {
+    let contents = fs::read_to_string(path)?;
+    decode_as(contents.trim_end(), kind)?;
+
+    Ok(())
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_dir_reports_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("logo.b64"), "aGVsbG8=\n").unwrap();
+        fs::write(dir.path().join("corrupt.b64"), "dfoiuerw892").unwrap();
+        fs::write(dir.path().join("notes.txt"), "not encoded at all").unwrap();
+
+        let results = validate_dir(dir.path(), EncodingKind::Base64);
+
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].0, dir.path().join("corrupt.b64"));
+        assert!(matches!(results[0].1, Err(AncryptorError::Decode(_))));
+        assert_eq!(results[1], (dir.path().join("logo.b64"), Ok(())));
+    }
+
+    #[test]
+    fn test_validate_dir_walks_subdirectories() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir(dir.path().join("icons")).unwrap();
+        fs::write(dir.path().join("icons").join("icon.hex"), "6869").unwrap();
+
+        let results = validate_dir(dir.path(), EncodingKind::Hex);
+
+        assert_eq!(results, vec![(dir.path().join("icons").join("icon.hex"), Ok(()))]);
+    }
+
+    #[test]
+    fn test_validate_dir_missing_directory() {
+        let missing = Path::new("/definitely/not/here");
+
+        let results = validate_dir(missing, EncodingKind::Base64);
+
+        assert_eq!(results.len(), 1);
+        assert!(matches!(results[0].1, Err(AncryptorError::Io(_, _))));
+    }
+}
@@ -0,0 +1,623 @@
+use crate::error::{AncryptorError, DecodeError};
+
+// Every frame starts with its length as a big-endian u32.
+const LENGTH_PREFIX_LEN: usize = 4;
+
+// Widest number `decode_bignum` restores, far above any key or field
+// element, so a forged width cannot make it allocate without limit.
+pub const MAX_BIGNUM_WIDTH: usize = 64 * 1024;
+
+///
+/// Encodes several byte slices into a single base64 string, prefixing
+/// each one with its length so [`decode_framed`] can split them again.
+///
+/// ## Panics
+///
+/// Panics if a part is 4 GiB or longer, as its length would not fit in
+/// the u32 prefix.
+///
+/// ## Example
+/// ```
+/// let framed = ancryptor::encode_framed(&[b"id", b"payload"]);
+/// let parts = ancryptor::decode_framed(&framed).unwrap();
+///
+/// assert_eq!(parts, vec![b"id".to_vec(), b"payload".to_vec()]);
+/// ```
+pub fn encode_framed(parts: &[&[u8]]) -> String {
+    let total_len = parts.iter().map(|part| LENGTH_PREFIX_LEN + part.len()).sum();
+    let mut framed = Vec::with_capacity(total_len);
+
+    for part in parts {
+        framed.extend_from_slice(&length_prefix(part));
+        framed.extend_from_slice(part);
+    }
+
+    crate::encode_bytes(&framed)
+}
+
+///
+/// Decodes the output of [`encode_framed`] back into its parts.
+///
+/// Returns [`AncryptorError::MalformedFrame`] if a length prefix is cut
+/// short or claims more bytes than remain.
+///
+pub fn decode_framed(from: &str) -> Result<Vec<Vec<u8>>, AncryptorError> {
+    let framed = crate::decode_bytes(from)?;
+    let mut remaining = framed.as_slice();
+    let mut parts = Vec::new();
+
+    while !remaining.is_empty() {
+        if remaining.len() < LENGTH_PREFIX_LEN {
+            return Err(AncryptorError::MalformedFrame);
+        }
+
+        let (prefix, rest) = remaining.split_at(LENGTH_PREFIX_LEN);
+        let part_len = u32::from_be_bytes(prefix.try_into().unwrap()) as usize;
+
+        if rest.len() < part_len {
+            return Err(AncryptorError::MalformedFrame);
+        }
+
+        let (part, rest) = rest.split_at(part_len);
+        parts.push(part.to_vec());
+        remaining = rest;
+    }
+
+    Ok(parts)
+}
+
+///
+/// Like [`encode_framed`], but writes `sync_marker` in front of every
+/// frame so that [`decode_framed_recoverable`] can skip over corrupt
+/// frames and pick up again at the next one.
+///
+/// Pick a marker that is unlikely to appear inside the parts, e.g. a few
+/// random bytes fixed for the log format.
+///
+/// ## Panics
+///
+/// Panics if a part is 4 GiB or longer, like [`encode_framed`].
+///
+pub fn encode_framed_synced(parts: &[&[u8]], sync_marker: &[u8]) -> String {
+    let total_len = parts.iter().map(|part| sync_marker.len() + LENGTH_PREFIX_LEN + part.len()).sum();
+    let mut framed = Vec::with_capacity(total_len);
+
+    for part in parts {
+        framed.extend_from_slice(sync_marker);
+        framed.extend_from_slice(&length_prefix(part));
+        framed.extend_from_slice(part);
+    }
+
+    crate::encode_bytes(&framed)
+}
+
+///
+/// Best-effort decoding of the output of [`encode_framed_synced`], for
+/// partially corrupted logs.
+///
+/// A frame is accepted when it starts with `sync_marker`, its length fits
+/// in the input, and it is followed by the end of the input or by the next
+/// marker. On a bad frame the decoder scans forward to the next marker and
+/// resumes. Returns the frames that parsed and the number of corrupt
+/// regions that were skipped.
+///
+/// Fails only if `s` is not base64 at all or `sync_marker` is empty.
+///
+/// ## Example
+/// ```
+/// let framed = ancryptor::encode_framed_synced(&[b"first", b"second"], b"\xF0\x9F");
+/// let (frames, skipped) = ancryptor::decode_framed_recoverable(&framed, b"\xF0\x9F").unwrap();
+///
+/// assert_eq!(frames, vec![b"first".to_vec(), b"second".to_vec()]);
+/// assert_eq!(skipped, 0);
+/// ```
+pub fn decode_framed_recoverable(s: &str, sync_marker: &[u8]) -> Result<(Vec<Vec<u8>>, usize), AncryptorError> {
+    if sync_marker.is_empty() {
+        return Err(AncryptorError::InvalidArgument("sync marker must not be empty".to_owned()));
+    }
+
+    let framed = crate::decode_bytes(s)?;
+    let mut position = 0;
+    let mut frames = Vec::new();
+    let mut skipped = 0;
+
+    while position < framed.len() {
+        match synced_frame_at(&framed, position, sync_marker) {
+            Some((frame, next_position)) => {
+                frames.push(frame.to_vec());
+                position = next_position;
+            }
+            None => {
+                skipped += 1;
+                position = find_marker(&framed, position + 1, sync_marker).unwrap_or(framed.len());
+            }
+        }
+    }
+
+    Ok((frames, skipped))
+}
+
+fn length_prefix(part: &[u8]) -> [u8; LENGTH_PREFIX_LEN] {
+    u32::try_from(part.len())
+        .expect("a framed part must be shorter than 4 GiB")
+        .to_be_bytes()
+}
+
+///
+/// Returns the frame starting at `position` and where the next one
+/// starts, if the frame is intact.
+///
+fn synced_frame_at<'a>(framed: &'a [u8], position: usize, sync_marker: &[u8]) -> Option<(&'a [u8], usize)> {
+    let rest = framed[position..].strip_prefix(sync_marker)?;
+    let prefix = rest.get(..LENGTH_PREFIX_LEN)?;
+    let part_len = u32::from_be_bytes(prefix.try_into().unwrap()) as usize;
+    let part = rest.get(LENGTH_PREFIX_LEN..LENGTH_PREFIX_LEN.checked_add(part_len)?)?;
+
+    let next_position = position + sync_marker.len() + LENGTH_PREFIX_LEN + part_len;
+    if next_position < framed.len() && !framed[next_position..].starts_with(sync_marker) {
+        return None;
+    }
+
+    Some((part, next_position))
+}
+
+fn find_marker(framed: &[u8], from: usize, sync_marker: &[u8]) -> Option<usize> {
+    framed.get(from..)?
+        .windows(sync_marker.len())
+        .position(|window| window == sync_marker)
+        .map(|offset| from + offset)
+}
+
+///
+/// Like [`encode_framed`], but prefixes each part with its length as an
+/// unsigned LEB128 varint (as protobuf does) instead of a fixed u32, so
+/// parts shorter than 128 bytes only cost one byte of overhead.
+///
+/// ## Example
+/// ```
+/// let framed = ancryptor::encode_varint_framed(&[b"id", b"payload"]);
+/// let parts = ancryptor::decode_varint_framed(&framed).unwrap();
+///
+/// assert_eq!(parts, vec![b"id".to_vec(), b"payload".to_vec()]);
+/// ```
+pub fn encode_varint_framed(parts: &[&[u8]]) -> String {
+    let mut framed = Vec::with_capacity(parts.iter().map(|part| part.len() + 1).sum());
+
+    for part in parts {
+        write_varint(&mut framed, part.len() as u64);
+        framed.extend_from_slice(part);
+    }
+
+    crate::encode_bytes(&framed)
+}
+
+///
+/// Decodes the output of [`encode_varint_framed`] back into its parts.
+///
+/// Returns [`AncryptorError::MalformedFrame`] if a varint is truncated,
+/// overflows 64 bits, or claims more bytes than remain.
+///
+pub fn decode_varint_framed(from: &str) -> Result<Vec<Vec<u8>>, AncryptorError> {
+    let framed = crate::decode_bytes(from)?;
+    let mut remaining = framed.as_slice();
+    let mut parts = Vec::new();
+
+    while !remaining.is_empty() {
+        let (part_len, rest) = read_varint(remaining)?;
+        let part_len = usize::try_from(part_len).map_err(|_| AncryptorError::MalformedFrame)?;
+
+        if rest.len() < part_len {
+            return Err(AncryptorError::MalformedFrame);
+        }
+
+        let (part, rest) = rest.split_at(part_len);
+        parts.push(part.to_vec());
+        remaining = rest;
+    }
+
+    Ok(parts)
+}
+
+///
+/// Encodes a big-endian number as base64, dropping its leading zero bytes
+/// but recording its original width, so [`decode_bignum`] can restore
+/// them exactly.
+///
+/// Fixed-width values such as keys or field elements keep their size this
+/// way, even when they happen to start with zero bytes.
+///
+/// [`decode_bignum`] rejects widths above [`MAX_BIGNUM_WIDTH`] bytes.
+///
+/// ## Example
+/// ```
+/// let encoded = ancryptor::encode_bignum(&[0x00, 0x00, 0x05]);
+///
+/// assert_eq!(ancryptor::decode_bignum(&encoded).unwrap(), [0x00, 0x00, 0x05]);
+/// ```
+pub fn encode_bignum(bytes: &[u8]) -> String {
+    let significant_start = bytes.iter().position(|byte| *byte != 0).unwrap_or(bytes.len());
+    let significant = &bytes[significant_start..];
+
+    let mut payload = Vec::with_capacity(significant.len() + 1);
+    write_varint(&mut payload, bytes.len() as u64);
+    payload.extend_from_slice(significant);
+
+    crate::encode_bytes(&payload)
+}
+
+///
+/// Decodes the output of [`encode_bignum`], padding the number back to
+/// its original width with leading zero bytes.
+///
+/// Returns [`AncryptorError::MalformedFrame`] if the recorded width is
+/// truncated, smaller than the number it describes, or larger than
+/// [`MAX_BIGNUM_WIDTH`].
+///
+pub fn decode_bignum(from: &str) -> Result<Vec<u8>, AncryptorError> {
+    let payload = crate::decode_bytes(from)?;
+    let (width, significant) = read_varint(&payload)?;
+    let width = usize::try_from(width).map_err(|_| AncryptorError::MalformedFrame)?;
+
+    if significant.len() > width || width > MAX_BIGNUM_WIDTH {
+        return Err(AncryptorError::MalformedFrame);
+    }
+
+    let mut bytes = vec![0; width - significant.len()];
+    bytes.extend_from_slice(significant);
+
+    Ok(bytes)
+}
+
+///
+/// Encodes a fixed-width value as base64 without its trailing zero bytes,
+/// for sparse fields that are usually much shorter than their width.
+///
+/// Unlike [`encode_bignum`] the width is not stored: the caller passes
+/// the same `width` to [`decode_trimmed`] to get the zeros back.
+///
+/// Returns [`AncryptorError::InvalidArgument`] if `data` is longer than
+/// `width`.
+///
+/// ## Example
+/// ```
+/// let encoded = ancryptor::encode_trimmed(&[0x05, 0x00, 0x00, 0x00], 4).unwrap();
+///
+/// assert_eq!(encoded, "BQ==");
+/// assert_eq!(ancryptor::decode_trimmed(&encoded, 4).unwrap(), [0x05, 0x00, 0x00, 0x00]);
+/// ```
+pub fn encode_trimmed(data: &[u8], width: usize) -> Result<String, AncryptorError> {
+    if data.len() > width {
+        return Err(AncryptorError::InvalidArgument(
+            format!("data is {} bytes, longer than its width of {}", data.len(), width)
+        ));
+    }
+
+    let significant_end = data.iter().rposition(|byte| *byte != 0).map_or(0, |index| index + 1);
+
+    Ok(crate::encode_bytes(&data[..significant_end]))
+}
+
+///
+/// Decodes the output of [`encode_trimmed`], padding it back to `width`
+/// with trailing zero bytes.
+///
+/// Returns [`AncryptorError::InvalidArgument`] if the decoded value is
+/// already longer than `width`.
+///
+pub fn decode_trimmed(from: &str, width: usize) -> Result<Vec<u8>, AncryptorError> {
+    let mut data = crate::decode_bytes(from)?;
+
+    if data.len() > width {
+        return Err(AncryptorError::InvalidArgument(
+            format!("decoded value is {} bytes, longer than its width of {}", data.len(), width)
+        ));
+    }
+
+    data.resize(width, 0);
+    Ok(data)
+}
+
+// Seven bits per byte, least significant group first; the high bit is
+// set on every byte except the last.
+fn write_varint(output: &mut Vec<u8>, mut value: u64) {
+    while value >= 0x80 {
+        output.push((value as u8 & 0x7F) | 0x80);
+        value >>= 7;
+    }
+    output.push(value as u8);
+}
+
+fn read_varint(input: &[u8]) -> Result<(u64, &[u8]), AncryptorError> {
+    let mut value: u64 = 0;
+
+    for (index, byte) in input.iter().enumerate() {
+        let shift = 7 * index as u32;
+        let bits = (byte & 0x7F) as u64;
+
+        // A u64 needs at most ten bytes, and the tenth may only carry one bit.
+        if shift >= 64 || (shift == 63 && bits > 1) {
+            return Err(AncryptorError::MalformedFrame);
+        }
+
+        value |= bits << shift;
+
+        if byte & 0x80 == 0 {
+            return Ok((value, &input[index + 1..]));
+        }
+    }
+
+    // Ran out of input while the continuation bit was still set.
+    Err(AncryptorError::MalformedFrame)
+}
+
+///
+/// A value that can be stored as a single frame.
+///
+/// Numbers are stored big-endian, `bool` as one byte, and
+/// `String`/`Vec<u8>` as their raw bytes.
+///
+pub trait FrameField: Sized {
+    fn to_frame(&self) -> Vec<u8>;
+    fn from_frame(frame: &[u8]) -> Result<Self, AncryptorError>;
+}
+
+macro_rules! impl_frame_field_for_numbers {
+    ($($number:ty),*) => {
+        $(
+            impl FrameField for $number {
+                fn to_frame(&self) -> Vec<u8> {
+                    self.to_be_bytes().to_vec()
+                }
+
+                fn from_frame(frame: &[u8]) -> Result<Self, AncryptorError> {
+                    let bytes = frame.try_into().map_err(|_| AncryptorError::MalformedFrame)?;
+                    Ok(<$number>::from_be_bytes(bytes))
+                }
+            }
+        )*
+    };
+}
+
+impl_frame_field_for_numbers!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128, f32, f64);
+
+impl FrameField for bool {
+    fn to_frame(&self) -> Vec<u8> {
+        vec![*self as u8]
+    }
+
+    fn from_frame(frame: &[u8]) -> Result<Self, AncryptorError> {
+        match frame {
+            [0] => Ok(false),
+            [1] => Ok(true),
+            _ => Err(AncryptorError::MalformedFrame),
+        }
+    }
+}
+
+impl FrameField for Vec<u8> {
+    fn to_frame(&self) -> Vec<u8> {
+        self.clone()
+    }
+
+    fn from_frame(frame: &[u8]) -> Result<Self, AncryptorError> {
+        Ok(frame.to_vec())
+    }
+}
+
+impl FrameField for String {
+    fn to_frame(&self) -> Vec<u8> {
+        self.as_bytes().to_vec()
+    }
+
+    fn from_frame(frame: &[u8]) -> Result<Self, AncryptorError> {
+        String::from_utf8(frame.to_vec()).map_err(|error| DecodeError::InvalidUtf8(error).into())
+    }
+}
+
+///
+/// A type that can be encoded into, and decoded from, a framed base64
+/// string - one frame per field.
+///
+/// Usually implemented with `#[derive(Encodable)]` from the
+/// `ancryptor_derive` crate rather than by hand.
+///
+pub trait Encodable: Sized {
+    fn encode(&self) -> String;
+    fn decode(from: &str) -> Result<Self, AncryptorError>;
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SYNC: &[u8] = b"\xF0\x9F\x8C\x80";
+
+    #[test]
+    fn test_framed_round_trip_keeps_empty_parts() {
+        let framed = encode_framed(&[b"", b"a", b"bc"]);
+
+        assert_eq!(decode_framed(&framed).unwrap(), vec![vec![], b"a".to_vec(), b"bc".to_vec()]);
+    }
+
+    #[test]
+    fn test_framed_truncated_part_fails() {
+        // Claims 5 bytes but only carries 2.
+        let framed = crate::encode_bytes(&[0, 0, 0, 5, 1, 2]);
+
+        assert_eq!(decode_framed(&framed), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_frame_field_number_round_trip() {
+        let frame = 0xDEAD_BEEF_u32.to_frame();
+
+        assert_eq!(frame, vec![0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(u32::from_frame(&frame), Ok(0xDEAD_BEEF));
+        assert_eq!(u64::from_frame(&frame), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_varint_framed_round_trip_varying_sizes() {
+        let sizes = [0, 1, 127, 128, 300, 16_384, 70_000];
+        let owned: Vec<Vec<u8>> = sizes.iter().map(|size| vec![0xA5; *size]).collect();
+        let parts: Vec<&[u8]> = owned.iter().map(|part| part.as_slice()).collect();
+
+        let framed = encode_varint_framed(&parts);
+
+        assert_eq!(decode_varint_framed(&framed).unwrap(), owned);
+    }
+
+    #[test]
+    fn test_varint_prefix_lengths() {
+        for (value, expected) in [(0, vec![0x00]), (127, vec![0x7F]), (128, vec![0x80, 0x01]), (300, vec![0xAC, 0x02])] {
+            let mut encoded = Vec::new();
+            write_varint(&mut encoded, value);
+
+            assert_eq!(encoded, expected, "varint of {}", value);
+            assert_eq!(read_varint(&encoded).unwrap(), (value, &[][..]));
+        }
+    }
+
+    #[test]
+    fn test_varint_framed_is_smaller_than_u32_framing() {
+        let parts: [&[u8]; 3] = [b"a", b"bc", b"def"];
+
+        assert!(encode_varint_framed(&parts).len() < encode_framed(&parts).len());
+    }
+
+    #[test]
+    fn test_varint_framed_truncated_varint_fails() {
+        // A continuation bit with nothing after it.
+        let framed = crate::encode_bytes(&[0x80]);
+
+        assert_eq!(decode_varint_framed(&framed), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_varint_framed_overlong_varint_fails() {
+        let framed = crate::encode_bytes(&[0xFF; 11]);
+
+        assert_eq!(decode_varint_framed(&framed), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_varint_framed_truncated_part_fails() {
+        let framed = crate::encode_bytes(&[0x05, b'a', b'b']);
+
+        assert_eq!(decode_varint_framed(&framed), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_bignum_keeps_leading_zeros() {
+        let encoded = encode_bignum(&[0x00, 0x00, 0x05]);
+
+        assert_eq!(decode_bignum(&encoded).unwrap(), [0x00, 0x00, 0x05]);
+        assert_ne!(decode_bignum(&encoded).unwrap(), [0x05]);
+    }
+
+    #[test]
+    fn test_bignum_edge_widths() {
+        for bytes in [&[][..], &[0x00], &[0x00; 32], &[0xFF, 0x00], &[0x01, 0x02, 0x03]] {
+            assert_eq!(decode_bignum(&encode_bignum(bytes)).unwrap(), bytes);
+        }
+    }
+
+    #[test]
+    fn test_bignum_width_smaller_than_number_fails() {
+        let encoded = crate::encode_bytes(&[0x01, 0xAA, 0xBB]);
+
+        assert_eq!(decode_bignum(&encoded), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_bignum_huge_width_fails_without_allocating() {
+        // A varint width of 2^62 followed by no number at all.
+        let encoded = crate::encode_bytes(&[0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x80, 0x40]);
+
+        assert_eq!(decode_bignum(&encoded), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_bignum_max_width_round_trips() {
+        let mut bytes = vec![0x00; MAX_BIGNUM_WIDTH];
+        bytes[MAX_BIGNUM_WIDTH - 1] = 0x01;
+
+        assert_eq!(decode_bignum(&encode_bignum(&bytes)).unwrap(), bytes);
+        assert_eq!(decode_bignum(&encode_bignum(&[0x00; MAX_BIGNUM_WIDTH + 1])), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_bignum_missing_width_fails() {
+        assert_eq!(decode_bignum(""), Err(AncryptorError::MalformedFrame));
+    }
+
+    #[test]
+    fn test_trimmed_restores_trailing_zeros() {
+        let field = [0x07, 0x00, 0x09, 0x00, 0x00, 0x00, 0x00, 0x00];
+
+        let encoded = encode_trimmed(&field, field.len()).unwrap();
+
+        assert_eq!(encoded, crate::encode_bytes(&[0x07, 0x00, 0x09]));
+        assert_eq!(decode_trimmed(&encoded, field.len()).unwrap(), field);
+    }
+
+    #[test]
+    fn test_trimmed_all_zeros_and_short_input() {
+        assert_eq!(encode_trimmed(&[0x00; 16], 16).unwrap(), "");
+        assert_eq!(decode_trimmed("", 16).unwrap(), [0x00; 16]);
+        assert_eq!(decode_trimmed(&encode_trimmed(&[0x01], 4).unwrap(), 4).unwrap(), [0x01, 0x00, 0x00, 0x00]);
+    }
+
+    #[test]
+    fn test_trimmed_value_wider_than_width_fails() {
+        let encoded = encode_trimmed(&[0x01, 0x02, 0x03], 3).unwrap();
+
+        assert!(matches!(decode_trimmed(&encoded, 2), Err(AncryptorError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_trimmed_encode_rejects_data_wider_than_width() {
+        assert!(matches!(encode_trimmed(&[0x01, 0x02, 0x03], 2), Err(AncryptorError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_recoverable_skips_one_corrupt_frame_between_valid_ones() {
+        let framed = encode_framed_synced(&[b"first", b"corrupted", b"third"], SYNC);
+        let mut bytes = crate::decode_bytes(&framed).unwrap();
+
+        // Break the length prefix of the middle frame.
+        let middle = SYNC.len() + LENGTH_PREFIX_LEN + b"first".len();
+        bytes[middle + SYNC.len()] = 0x7F;
+
+        let (frames, skipped) = decode_framed_recoverable(&crate::encode_bytes(&bytes), SYNC).unwrap();
+
+        assert_eq!(frames, vec![b"first".to_vec(), b"third".to_vec()]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn test_recoverable_intact_input_skips_nothing() {
+        let framed = encode_framed_synced(&[b"a", b"", b"bc"], SYNC);
+
+        assert_eq!(decode_framed_recoverable(&framed, SYNC).unwrap(), (vec![b"a".to_vec(), vec![], b"bc".to_vec()], 0));
+    }
+
+    #[test]
+    fn test_recoverable_garbage_before_first_marker() {
+        let mut bytes = b"garbage".to_vec();
+        bytes.extend_from_slice(&crate::decode_bytes(&encode_framed_synced(&[b"kept"], SYNC)).unwrap());
+
+        let (frames, skipped) = decode_framed_recoverable(&crate::encode_bytes(&bytes), SYNC).unwrap();
+
+        assert_eq!(frames, vec![b"kept".to_vec()]);
+        assert_eq!(skipped, 1);
+    }
+
+    #[test]
+    fn test_recoverable_rejects_empty_marker() {
+        assert!(matches!(decode_framed_recoverable("", b""), Err(AncryptorError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,91 @@
+use std::fmt::Write;
+
+static BYTES_PER_ROW: usize = 16;
+
+///
+/// Renders `data` the way `hexdump -C` does: an offset column, sixteen
+/// hex bytes split into two groups of eight, and the printable ASCII
+/// characters (`.` for anything else). The last line holds the total
+/// length.
+///
+/// Unlike `hexdump`, repeated rows are printed in full rather than
+/// collapsed into `*`.
+///
+/// ## Example
+/// ```
+/// let dump = ancryptor::hexdump(b"hi!\n");
+///
+/// assert_eq!(dump, "\
+/// 00000000  68 69 21 0a                                       |hi!.|
+/// 00000004
+/// ");
+/// ```
+pub fn hexdump(data: &[u8]) -> String {
+    if data.is_empty() {
+        return String::new();
+    }
+
+    let mut dump = String::new();
+
+    for (row_index, row) in data.chunks(BYTES_PER_ROW).enumerate() {
+        write!(dump, "{:08x} ", row_index * BYTES_PER_ROW).unwrap();
+
+        for column in 0..BYTES_PER_ROW {
+            if column == BYTES_PER_ROW / 2 {
+                dump.push(' ');
+            }
+
+            match row.get(column) {
+                Some(byte) => write!(dump, " {:02x}", byte).unwrap(),
+                None => dump.push_str("   "),
+            }
+        }
+
+        let ascii: String = row.iter()
+            .map(|byte| if byte.is_ascii_graphic() || *byte == b' ' { *byte as char } else { '.' })
+            .collect();
+        writeln!(dump, "  |{}|", ascii).unwrap();
+    }
+
+    writeln!(dump, "{:08x}", data.len()).unwrap();
+    dump
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_hexdump_first_row_format() {
+        let dump = hexdump(b"hello_world_from_rust");
+        let first_row = dump.lines().next().unwrap();
+
+        assert_eq!(first_row, "00000000  68 65 6c 6c 6f 5f 77 6f  72 6c 64 5f 66 72 6f 6d  |hello_world_from|");
+    }
+
+    #[test]
+    fn test_hexdump_partial_last_row_and_length() {
+        let dump = hexdump(b"hello_world_from_rust");
+        let lines: Vec<&str> = dump.lines().collect();
+
+        assert_eq!(lines[1], "00000010  5f 72 75 73 74                                    |_rust|");
+        assert_eq!(lines[2], "00000015");
+        assert_eq!(lines.len(), 3);
+    }
+
+    #[test]
+    fn test_hexdump_non_printable_bytes() {
+        let dump = hexdump(&[0x00, 0x7F, 0xFF, b' ']);
+
+        assert!(dump.starts_with("00000000  00 7f ff 20 "));
+        assert!(dump.contains("|... |"));
+    }
+
+    #[test]
+    fn test_hexdump_empty() {
+        assert_eq!(hexdump(&[]), "");
+    }
+}
@@ -0,0 +1,261 @@
+use alloc::string::String;
+use alloc::vec::Vec;
+
+use base64::{
+    Engine as _,
+    engine::general_purpose::STANDARD as base64Engine
+};
+
+use crate::error::DecodeError;
+
+// Bytes per base64 group, and the characters they encode to.
+const BYTE_GROUP_LEN: usize = 3;
+const TEXT_GROUP_LEN: usize = 4;
+
+///
+/// Encodes to standard, padded base64 a chunk at a time, e.g. as data
+/// arrives from a socket, without a `Read`/`Write` pair or buffering the
+/// whole payload.
+///
+/// Up to 2 bytes that do not fill a 3-byte group yet are kept for the
+/// next [`Encoder::update`], so the concatenated output is identical to
+/// [`crate::encode_bytes`] of all the chunks, however they were split.
+///
+/// ## Example
+/// ```
+/// use ancryptor::Encoder;
+///
+/// let mut encoder = Encoder::new();
+/// let mut encoded = encoder.update(b"hello_");
+/// encoded.push_str(&encoder.update(b"world"));
+/// encoded.push_str(&encoder.finalize());
+///
+/// assert_eq!(encoded, ancryptor::encode_bytes(b"hello_world"));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Encoder {
+    // Fewer than `BYTE_GROUP_LEN` bytes, waiting for the rest of their group.
+    pending: Vec<u8>,
+}
+
+impl Encoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Encodes every complete 3-byte group of the pending bytes followed
+    /// by `chunk`, and keeps the rest.
+    ///
+    pub fn update(&mut self, chunk: &[u8]) -> String {
+        let mut output = String::new();
+        let chunk = complete_pending_group(&mut self.pending, chunk, BYTE_GROUP_LEN);
+
+        if self.pending.len() == BYTE_GROUP_LEN {
+            base64Engine.encode_string(&self.pending, &mut output);
+            self.pending.clear();
+        }
+
+        let whole_groups_len = chunk.len() - chunk.len() % BYTE_GROUP_LEN;
+        base64Engine.encode_string(&chunk[..whole_groups_len], &mut output);
+        self.pending.extend_from_slice(&chunk[whole_groups_len..]);
+
+        output
+    }
+
+    ///
+    /// Encodes the pending bytes, with padding, ending the output.
+    ///
+    pub fn finalize(self) -> String {
+        base64Engine.encode(self.pending)
+    }
+}
+
+///
+/// Decodes standard, padded base64 a chunk at a time, the counterpart of
+/// [`Encoder`].
+///
+/// Up to 3 characters that do not fill a 4-character group yet are kept
+/// for the next [`Decoder::update`]. Like [`crate::decode_bytes`] it is
+/// strict: whitespace is invalid, and so is anything after the padding.
+/// A missing end only shows up in [`Decoder::finalize`].
+///
+/// ## Example
+/// ```
+/// use ancryptor::Decoder;
+///
+/// let mut decoder = Decoder::new();
+/// let mut decoded = decoder.update(b"aGVsbG9f").unwrap();
+/// decoded.extend(decoder.update(b"d29ybGQ=").unwrap());
+/// decoded.extend(decoder.finalize().unwrap());
+///
+/// assert_eq!(decoded, b"hello_world");
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct Decoder {
+    // Fewer than `TEXT_GROUP_LEN` characters, waiting for the rest of their group.
+    pending: Vec<u8>,
+    // A padded group was decoded, so the input must end here.
+    padded: bool,
+}
+
+impl Decoder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    ///
+    /// Decodes every complete 4-character group of the pending characters
+    /// followed by `chunk`, and keeps the rest.
+    ///
+    /// Error offsets are relative to the groups decoded by this call.
+    ///
+    pub fn update(&mut self, chunk: &[u8]) -> Result<Vec<u8>, DecodeError> {
+        let mut output = Vec::new();
+        let chunk = complete_pending_group(&mut self.pending, chunk, TEXT_GROUP_LEN);
+
+        if self.pending.len() == TEXT_GROUP_LEN {
+            let group = core::mem::take(&mut self.pending);
+            self.decode_groups(&group, &mut output)?;
+        }
+
+        let whole_groups_len = chunk.len() - chunk.len() % TEXT_GROUP_LEN;
+        self.decode_groups(&chunk[..whole_groups_len], &mut output)?;
+        self.pending.extend_from_slice(&chunk[whole_groups_len..]);
+
+        Ok(output)
+    }
+
+    ///
+    /// Checks that the input ended on a complete group, ending the output.
+    ///
+    pub fn finalize(mut self) -> Result<Vec<u8>, DecodeError> {
+        let mut output = Vec::new();
+        let pending = core::mem::take(&mut self.pending);
+
+        self.decode_groups(&pending, &mut output)?;
+
+        Ok(output)
+    }
+
+    fn decode_groups(&mut self, groups: &[u8], output: &mut Vec<u8>) -> Result<(), DecodeError> {
+        if groups.is_empty() {
+            return Ok(());
+        }
+        if self.padded {
+            return Err(DecodeError::InvalidBase64(base64::DecodeError::InvalidPadding));
+        }
+
+        base64Engine.decode_vec(groups, output).map_err(DecodeError::InvalidBase64)?;
+        self.padded = groups.ends_with(b"=");
+
+        Ok(())
+    }
+}
+
+///
+/// Moves the head of `chunk` into `pending` until `pending` holds a
+/// whole group (or `chunk` runs out), returning what is left of `chunk`.
+///
+fn complete_pending_group<'a>(pending: &mut Vec<u8>, chunk: &'a [u8], group_len: usize) -> &'a [u8] {
+    if pending.is_empty() {
+        return chunk;
+    }
+
+    let (head, rest) = chunk.split_at((group_len - pending.len()).min(chunk.len()));
+    pending.extend_from_slice(head);
+
+    rest
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn encode_in_chunks(data: &[u8], chunk_len: usize) -> String {
+        let mut encoder = Encoder::new();
+        let mut encoded: String = data.chunks(chunk_len).map(|chunk| encoder.update(chunk)).collect();
+        encoded.push_str(&encoder.finalize());
+        encoded
+    }
+
+    fn decode_in_chunks(text: &[u8], chunk_len: usize) -> Result<Vec<u8>, DecodeError> {
+        let mut decoder = Decoder::new();
+        let mut decoded = Vec::new();
+        for chunk in text.chunks(chunk_len) {
+            decoded.extend(decoder.update(chunk)?);
+        }
+        decoded.extend(decoder.finalize()?);
+        Ok(decoded)
+    }
+
+    #[test]
+    fn test_encoder_one_chunk_and_byte_by_byte_are_identical() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        for len in [0, 1, 2, 3, 4, 5, 100, 256] {
+            let one_chunk = encode_in_chunks(&data[..len], len.max(1));
+
+            assert_eq!(one_chunk, crate::encode_bytes(&data[..len]));
+            assert_eq!(encode_in_chunks(&data[..len], 1), one_chunk, "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_encoder_any_chunk_size() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        for chunk_len in 1..=10 {
+            assert_eq!(encode_in_chunks(&data, chunk_len), crate::encode_bytes(&data), "chunk {}", chunk_len);
+        }
+    }
+
+    #[test]
+    fn test_encoder_empty_updates_output_nothing() {
+        let mut encoder = Encoder::new();
+
+        assert_eq!(encoder.update(b""), "");
+        assert_eq!(encoder.update(b"h"), "");
+        assert_eq!(encoder.update(b""), "");
+        assert_eq!(encoder.update(b"i!"), "aGkh");
+        assert_eq!(encoder.finalize(), "");
+    }
+
+    #[test]
+    fn test_decoder_one_chunk_and_byte_by_byte_are_identical() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        for len in [0, 1, 2, 3, 4, 5, 100, 256] {
+            let encoded = crate::encode_bytes(&data[..len]);
+
+            assert_eq!(decode_in_chunks(encoded.as_bytes(), encoded.len().max(1)), Ok(data[..len].to_vec()));
+            assert_eq!(decode_in_chunks(encoded.as_bytes(), 1), Ok(data[..len].to_vec()), "len {}", len);
+        }
+    }
+
+    #[test]
+    fn test_decoder_rejects_truncated_input_on_finalize() {
+        let mut decoder = Decoder::new();
+
+        assert_eq!(decoder.update(b"aGVsbG"), Ok(b"hel".to_vec()));
+        assert!(matches!(decoder.finalize(), Err(DecodeError::InvalidBase64(_))));
+    }
+
+    #[test]
+    fn test_decoder_rejects_data_after_padding() {
+        assert!(decode_in_chunks(b"aGk=aGk=", 8).is_err());
+        assert!(decode_in_chunks(b"aGk=aGk=", 1).is_err());
+        assert!(decode_in_chunks(b"aGk=a", 3).is_err());
+    }
+
+    #[test]
+    fn test_decoder_rejects_invalid_characters() {
+        for chunk_len in [1, 4, 11] {
+            assert!(decode_in_chunks(b"dfoiuerw892!", chunk_len).is_err());
+            assert!(decode_in_chunks(b"aGVs bG8=", chunk_len).is_err());
+        }
+    }
+}
@@ -0,0 +1,215 @@
+use crate::encoding::{alphabet_for, pad_char_for, EncodingKind};
+use crate::error::{AncryptorError, DecodeError};
+
+// RFC 2045 limits MIME base64 lines to 76 characters.
+const MIME_LINE_WIDTH: usize = 76;
+
+///
+/// Encodes `data` as standard base64 and inserts `sep` every `group`
+/// characters, which is easier for people to read back, e.g. `aGVs-bG9f`.
+///
+/// `group` must be greater than zero and `sep` must not be a base64
+/// character, otherwise the groups could not be told apart from the data.
+///
+/// ## Example
+/// ```
+/// let grouped = ancryptor::encode_grouped(b"hello_world", 4, '-').unwrap();
+///
+/// assert_eq!(grouped, "aGVs-bG9f-d29y-bGQ=");
+/// ```
+pub fn encode_grouped(data: &[u8], group: usize, sep: char) -> Result<String, AncryptorError> {
+    if group == 0 {
+        return Err(AncryptorError::InvalidArgument("group size must be greater than zero".to_owned()));
+    }
+    validate_separator(sep)?;
+
+    let encoded = crate::encode_bytes(data);
+    let groups: Vec<&str> = encoded.as_bytes()
+        .chunks(group)
+        // base64 output is ASCII, so every chunk is valid UTF-8.
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect();
+
+    Ok(groups.join(&sep.to_string()))
+}
+
+///
+/// Decodes the output of [`encode_grouped`], removing every `sep` first.
+///
+pub fn decode_grouped(from: &str, sep: char) -> Result<Vec<u8>, AncryptorError> {
+    validate_separator(sep)?;
+
+    let ungrouped: String = from.chars().filter(|character| *character != sep).collect();
+
+    Ok(crate::decode_bytes(&ungrouped)?)
+}
+
+///
+/// Encodes `data` as standard base64 split into lines of `width`
+/// characters joined by `line_ending`, e.g. 64 for PEM or 76 for MIME.
+///
+/// The last line may be shorter and is not followed by a line ending.
+/// `width` must be greater than zero and `line_ending` must not be empty
+/// or contain base64 characters.
+///
+/// ## Example
+/// ```
+/// let wrapped = ancryptor::encode_wrapped(b"hello_world", 8, "\r\n").unwrap();
+///
+/// assert_eq!(wrapped, "aGVsbG9f\r\nd29ybGQ=");
+/// ```
+pub fn encode_wrapped(data: &[u8], width: usize, line_ending: &str) -> Result<String, AncryptorError> {
+    if width == 0 {
+        return Err(AncryptorError::InvalidArgument("line width must be greater than zero".to_owned()));
+    }
+    validate_line_ending(line_ending)?;
+
+    let encoded = crate::encode_bytes(data);
+    let lines: Vec<&str> = encoded.as_bytes()
+        .chunks(width)
+        // base64 output is ASCII, so every chunk is valid UTF-8.
+        .map(|chunk| std::str::from_utf8(chunk).unwrap())
+        .collect();
+
+    Ok(lines.join(line_ending))
+}
+
+///
+/// Decodes the output of [`encode_wrapped`], removing every `line_ending`
+/// first. Lines do not have to be of equal width.
+///
+pub fn decode_wrapped(from: &str, line_ending: &str) -> Result<Vec<u8>, AncryptorError> {
+    validate_line_ending(line_ending)?;
+
+    Ok(crate::decode_bytes(&from.replace(line_ending, ""))?)
+}
+
+///
+/// Encodes `data` as MIME base64 (RFC 2045): standard base64 split into
+/// lines of 76 characters separated by `\r\n`, e.g. for email bodies.
+///
+/// Unlike [`crate::encode_bytes`] the output spans several lines once it
+/// is longer than 76 characters; the last line has no line ending.
+///
+/// ## Example
+/// ```
+/// let mime = ancryptor::encode_mime(&[0; 60]);
+///
+/// assert_eq!(mime, format!("{}\r\n{}", "A".repeat(76), "A".repeat(4)));
+/// ```
+pub fn encode_mime(data: &[u8]) -> String {
+    encode_wrapped(data, MIME_LINE_WIDTH, "\r\n").expect("MIME line width and ending are valid")
+}
+
+///
+/// Decodes MIME base64 such as the output of [`encode_mime`], ignoring
+/// all ASCII whitespace, so lines may use `\r\n` or `\n` and have any
+/// width.
+///
+pub fn decode_mime(from: &str) -> Result<Vec<u8>, DecodeError> {
+    crate::decode_tolerant(from)
+}
+
+fn validate_line_ending(line_ending: &str) -> Result<(), AncryptorError> {
+    if line_ending.is_empty() {
+        return Err(AncryptorError::InvalidArgument("line ending must not be empty".to_owned()));
+    }
+
+    line_ending.chars().try_for_each(validate_separator)
+}
+
+fn validate_separator(sep: char) -> Result<(), AncryptorError> {
+    if alphabet_for(EncodingKind::Base64).contains(sep) || pad_char_for(EncodingKind::Base64) == Some(sep) {
+        return Err(AncryptorError::InvalidArgument(format!("separator '{}' is part of the base64 alphabet", sep)));
+    }
+
+    Ok(())
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_grouped_round_trip() {
+        let data = b"hello_world_from_rust";
+        let grouped = encode_grouped(data, 4, '-').unwrap();
+
+        assert_eq!(grouped, "aGVs-bG9f-d29y-bGRf-ZnJv-bV9y-dXN0");
+        assert_eq!(decode_grouped(&grouped, '-').unwrap(), data);
+    }
+
+    #[test]
+    fn test_grouped_rejects_zero_group() {
+        assert!(matches!(encode_grouped(b"data", 0, '-'), Err(AncryptorError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_grouped_rejects_separator_from_alphabet() {
+        for sep in ['A', 'z', '7', '+', '/', '='] {
+            assert!(matches!(encode_grouped(b"data", 4, sep), Err(AncryptorError::InvalidArgument(_))));
+            assert!(matches!(decode_grouped("ZGF0YQ==", sep), Err(AncryptorError::InvalidArgument(_))));
+        }
+    }
+
+    #[test]
+    fn test_wrapped_widths_and_line_endings() {
+        let data: Vec<u8> = (0..=255).collect();
+
+        for width in [64, 80] {
+            for line_ending in ["\n", "\r\n"] {
+                let wrapped = encode_wrapped(&data, width, line_ending).unwrap();
+                let lines: Vec<&str> = wrapped.split(line_ending).collect();
+
+                assert!(lines[..lines.len() - 1].iter().all(|line| line.len() == width), "width {}", width);
+                assert!(lines.last().unwrap().len() <= width);
+                assert!(!wrapped.ends_with(line_ending));
+                assert_eq!(decode_wrapped(&wrapped, line_ending).unwrap(), data);
+            }
+        }
+    }
+
+    #[test]
+    fn test_mime_wraps_at_76_characters() {
+        let data: Vec<u8> = (0..200).map(|byte| byte as u8).collect();
+        let mime = encode_mime(&data);
+        let lines: Vec<&str> = mime.split("\r\n").collect();
+
+        // 200 bytes encode to 268 characters: three full lines and 40 more.
+        assert_eq!(lines.len(), 4);
+        assert!(lines[..3].iter().all(|line| line.len() == 76));
+        assert_eq!(lines[3].len(), 40);
+        assert_eq!(mime.matches('\n').count(), 3);
+        assert_eq!(decode_mime(&mime).unwrap(), data);
+    }
+
+    #[test]
+    fn test_decode_mime_ignores_any_whitespace() {
+        let mime = encode_mime(&[7; 200]);
+
+        assert_eq!(decode_mime(&mime.replace("\r\n", "\n")).unwrap(), [7; 200]);
+        assert_eq!(decode_mime(&format!(" {}\r\n", mime)).unwrap(), [7; 200]);
+        assert!(matches!(decode_mime("not base64!"), Err(DecodeError::InvalidBase64(_))));
+    }
+
+    #[test]
+    fn test_wrapped_short_input_is_one_line() {
+        assert_eq!(encode_wrapped(b"hi", 64, "\n").unwrap(), "aGk=");
+    }
+
+    #[test]
+    fn test_wrapped_rejects_zero_width() {
+        assert!(matches!(encode_wrapped(b"data", 0, "\n"), Err(AncryptorError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_wrapped_rejects_bad_line_endings() {
+        for line_ending in ["", "A", "\n=", "+"] {
+            assert!(matches!(encode_wrapped(b"data", 4, line_ending), Err(AncryptorError::InvalidArgument(_))));
+            assert!(matches!(decode_wrapped("ZGF0YQ==", line_ending), Err(AncryptorError::InvalidArgument(_))));
+        }
+    }
+}
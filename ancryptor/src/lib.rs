@@ -1,69 +1,484 @@
+// Without the default `std` feature only the base64/base32/hex core is
+// built, which needs nothing but `alloc`.
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+mod backend;
+#[cfg(feature = "base58")]
+mod base58;
+#[cfg(feature = "std")]
+mod batch;
+#[cfg(feature = "std")]
+mod checksum;
+#[cfg(feature = "std")]
+mod codec;
+#[cfg(feature = "compression")]
+mod compression;
+#[cfg(feature = "std")]
+mod crypto;
+#[cfg(feature = "std")]
+mod digest;
+mod encoding;
+#[cfg(feature = "std")]
+mod entropy;
+#[cfg(feature = "std")]
+mod envelope;
+mod error;
+#[cfg(feature = "std")]
+mod files;
+#[cfg(feature = "std")]
+mod framing;
+#[cfg(feature = "std")]
+mod hexdump;
+mod incremental;
+#[cfg(feature = "std")]
+mod layout;
+#[cfg(feature = "hmac")]
+mod mac;
+#[cfg(feature = "std")]
+mod mnemonic;
+#[cfg(feature = "std")]
+mod query;
+#[cfg(feature = "std")]
+mod random;
+#[cfg(feature = "std")]
+mod sanitize;
+#[cfg(feature = "std")]
+mod stream;
+#[cfg(feature = "std")]
+mod token;
+
+#[cfg(feature = "base58")]
+pub use base58::{
+    encode_base58,
+    decode_base58,
+};
+#[cfg(feature = "std")]
+pub use batch::encode_batch_dedup;
+#[cfg(feature = "rayon")]
+pub use batch::decode_batch_parallel;
+#[cfg(feature = "std")]
+pub use checksum::{
+    safe_encode,
+    safe_decode,
+};
+#[cfg(feature = "compression")]
+pub use compression::{
+    encode_compressed,
+    decode_compressed,
+};
+#[cfg(feature = "std")]
+pub use codec::{
+    Codec,
+    CustomCodec,
+    register_codec,
+    codec_by_name,
+};
+#[cfg(feature = "std")]
+pub use crypto::{
+    encrypt_convergent,
+    decrypt_convergent,
+    decrypt_convergent_zeroizing,
+    encrypt_aes_gcm_aad,
+    decrypt_aes_gcm_aad,
+    decrypt_aes_gcm_aad_zeroizing,
+    encrypt,
+    decrypt,
+    decrypt_zeroizing,
+    encrypt_with_password,
+    decrypt_with_password,
+    derive_subkey,
+};
+#[cfg(feature = "std")]
+pub use digest::{
+    DEFAULT_FINGERPRINT_LEN,
+    canonical_id,
+    short_fingerprint,
+    encode_fixed_from_hash,
+};
+pub use encoding::{
+    EncodingKind,
+    alphabet_for,
+    pad_char_for,
+    encoded_len,
+    max_input_bytes_for_output,
+    encode_as,
+    decode_as,
+    encode_adaptive,
+    decode_adaptive,
+    encode_base32,
+    encode_base32_lower,
+    decode_base32,
+    encode_hex,
+    decode_hex,
+    detect_encoding,
+};
+#[cfg(feature = "std")]
+pub use entropy::{
+    shannon_entropy,
+    decode_and_measure_entropy,
+};
+#[cfg(feature = "std")]
+pub use envelope::{
+    Algorithm,
+    Envelope,
+};
+pub use error::{
+    AncryptorError,
+    CodecError,
+    CryptoError,
+    DecodeError,
+    EncodeError,
+};
+#[cfg(feature = "std")]
+pub use files::validate_dir;
+#[cfg(feature = "std")]
+pub use framing::{
+    MAX_BIGNUM_WIDTH,
+    Encodable,
+    FrameField,
+    encode_framed,
+    decode_framed,
+    encode_framed_synced,
+    decode_framed_recoverable,
+    encode_varint_framed,
+    decode_varint_framed,
+    encode_bignum,
+    decode_bignum,
+    encode_trimmed,
+    decode_trimmed,
+};
+#[cfg(feature = "std")]
+pub use hexdump::hexdump;
+pub use incremental::{
+    Encoder,
+    Decoder,
+};
+#[cfg(feature = "std")]
+pub use layout::{
+    encode_grouped,
+    decode_grouped,
+    encode_wrapped,
+    decode_wrapped,
+    encode_mime,
+    decode_mime,
+};
+#[cfg(feature = "hmac")]
+pub use mac::{
+    sign,
+    verify,
+};
+#[cfg(feature = "std")]
+pub use mnemonic::{
+    encode_mnemonic,
+    decode_mnemonic,
+};
+#[cfg(feature = "std")]
+pub use query::decode_query_param;
+#[cfg(feature = "std")]
+pub use random::{
+    generate_key,
+    random_token,
+};
+#[cfg(feature = "std")]
+pub use sanitize::{
+    decode_html_unescaped,
+    decode_skip_comments,
+    decode_gnu_compatible,
+    gnu_decode_is_valid,
+};
+#[cfg(feature = "std")]
+pub use stream::{
+    transcode_reader,
+    encode_stream,
+    decode_stream,
+    encode_with_progress,
+};
+#[cfg(feature = "std")]
+pub use token::{
+    encode_and_sign,
+    verify_and_decode,
+    encode_expiring,
+    decode_expiring,
+    encode_timestamped,
+    decode_timestamped,
+};
+// Returned by `decrypt_zeroizing`, so callers can name it without
+// depending on `zeroize` themselves.
+#[cfg(feature = "std")]
+pub use zeroize::Zeroizing;
+
 use base64::{
-    Engine as _,
-    engine::general_purpose::STANDARD as base64Engine
+    Engine,
+    engine::general_purpose::STANDARD as base64Engine,
+    engine::general_purpose::URL_SAFE as base64UrlSafeEngine,
 };
+use subtle::ConstantTimeEq;
+
+use alloc::borrow::ToOwned;
+use alloc::string::String;
+use alloc::vec::Vec;
+
+// Some Windows tools (Notepad among them) prefix UTF-8 text with a byte-order mark.
+static UTF8_BOM: char = '\u{FEFF}';
+
+// Base64 characters validated at a time, a whole number of 4-character groups.
+const VALIDATION_CHUNK_LEN: usize = 1024;
 
+///
+/// Returns the version of this crate, e.g. to confirm which build of the
+/// native library an app actually loaded.
+///
+/// ## Example
+/// ```
+/// assert!(!ancryptor::version().is_empty());
+/// ```
+pub fn version() -> &'static str {
+    env!("CARGO_PKG_VERSION")
+}
+
+///
+/// Encodes `to` as standard, padded base64.
+///
+/// Never fails in practice; see [`try_encode`] for the one failure it
+/// hides.
+///
 pub fn encode(to: &str) -> String {
-    base64Engine.encode(String::from(to))
+    try_encode(to).unwrap_or_default()
+}
+
+///
+/// Appends the standard, padded base64 of `to` to `output`, for hot
+/// loops that reuse one buffer instead of allocating per call.
+///
+/// Nothing is allocated when `output` already has room for the
+/// [`encoded_len`] of the input.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{encoded_len, EncodingKind};
+///
+/// let mut output = String::with_capacity(encoded_len(2, EncodingKind::Base64));
+/// ancryptor::encode_into("hi", &mut output);
+///
+/// assert_eq!(output, "aGk=");
+/// ```
+pub fn encode_into(to: &str, output: &mut String) {
+    base64Engine.encode_string(to, output);
 }
 
+///
+/// Decodes standard, padded base64 into a `String`.
+///
+/// This is for UTF-8 text only. Invalid base64 and invalid UTF-8 both
+/// yield an empty string; use [`try_decode`] to tell them apart from a
+/// valid empty input, [`decode_lossy`] to keep the text around invalid
+/// sequences, and [`decode_bytes`] for binary data such as images.
+///
+/// Whitespace, including line breaks, is invalid too: use
+/// [`decode_tolerant`] for base64 that was wrapped or copied and pasted.
+///
 pub fn decode(from: &str) -> String {
-    let base64_bytes = base64Engine.decode(
-        String::from(from)
-    ).unwrap_or(vec![]);
+    try_decode(from).unwrap_or_default()
+}
 
-    match String::from_utf8(base64_bytes) {
-        Ok(result) => result,
-        Err(_) => "".to_owned()
+///
+/// Encodes `to` as standard, padded base64, reporting an error instead of
+/// panicking when the output would not fit in memory.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::try_encode("hi"), Ok("aGk=".to_owned()));
+/// ```
+pub fn try_encode(to: &str) -> Result<String, EncodeError> {
+    if base64::encoded_len(to.len(), true).is_none() {
+        return Err(EncodeError::OutputTooLong);
     }
+
+    Ok(backend::encode(to.as_bytes()))
 }
 
-pub fn add(left: usize, right: usize) -> usize {
-    left + right
+///
+/// Encodes `to` as url-safe, padded base64, which uses `-` and `_` in
+/// place of `+` and `/` so the result can go into URLs and file names.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::encode_url_safe("??>"), "Pz8-");
+/// assert_eq!(ancryptor::encode("??>"), "Pz8+");
+/// ```
+pub fn encode_url_safe(to: &str) -> String {
+    encode_as(to.as_bytes(), EncodingKind::Base64UrlSafe)
 }
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+///
+/// Decodes url-safe, padded base64 into a `String`.
+///
+/// Like [`decode`], invalid base64 and invalid UTF-8 yield an empty
+/// string, and a leading UTF-8 BOM is ignored. Standard base64
+/// containing `+` or `/` is invalid here.
+///
+pub fn decode_url_safe(from: &str) -> String {
+    decode_as(strip_bom(from), EncodingKind::Base64UrlSafe)
+        .ok()
+        .and_then(|bytes| String::from_utf8(bytes).ok())
+        .unwrap_or_default()
+}
+
+///
+/// Decodes base64 into a `String`, reporting why decoding failed instead
+/// of returning an empty string like [`decode`] does.
+///
+/// ## Example
+/// ```
+/// use ancryptor::DecodeError;
+///
+/// assert_eq!(ancryptor::try_decode("aGk=").unwrap(), "hi");
+/// assert!(matches!(ancryptor::try_decode("aGn/"), Err(DecodeError::InvalidUtf8(_))));
+/// ```
+pub fn try_decode(from: &str) -> Result<String, DecodeError> {
+    String::from_utf8(decode_bytes(from)?).map_err(DecodeError::InvalidUtf8)
+}
 
-    #[test]
-    fn it_works() {
-        let result = add(2, 2);
-        assert_eq!(result, 4);
+///
+/// Decodes base64 into a `String`, replacing invalid UTF-8 sequences with
+/// `U+FFFD` instead of discarding the whole result like [`decode`] does.
+///
+/// Invalid base64 still yields an empty string. Binary data is still
+/// damaged by the replacement: use [`decode_bytes`] for it.
+///
+/// ## Example
+/// ```
+/// // base64 of the bytes `h`, `i`, 0xFF
+/// assert_eq!(ancryptor::decode_lossy("aGn/"), "hi\u{FFFD}");
+/// ```
+pub fn decode_lossy(from: &str) -> String {
+    match decode_bytes(from) {
+        Ok(bytes) => String::from_utf8_lossy(&bytes).into_owned(),
+        Err(_) => "".to_owned()
     }
 }
 
+///
+/// Encodes raw bytes as standard, padded base64.
+///
+/// Unlike [`encode`] this accepts any bytes, not just UTF-8 text.
+///
+pub fn encode_bytes(data: &[u8]) -> String {
+    backend::encode(data)
+}
 
-enum BoxColor{
-    Red,
-    Blue,
-    Black
+///
+/// Decodes standard, padded base64 into raw bytes.
+///
+/// No UTF-8 conversion happens, so binary payloads come back untouched.
+/// A leading UTF-8 byte-order mark is ignored.
+///
+pub fn decode_bytes(from: &str) -> Result<Vec<u8>, DecodeError> {
+    backend::decode(strip_bom(from)).map_err(DecodeError::InvalidBase64)
 }
 
-struct Box {
-    dimentions: f64,
-    weight: f64,
-    color: BoxColor
+///
+/// Consuming version of [`decode_bytes`], for pipelines that move their
+/// `String`s around and have no use for the input afterwards.
+///
+/// The input is dropped once decoded; its buffer is not reused for the
+/// output.
+///
+pub fn decode_owned(from: String) -> Result<Vec<u8>, DecodeError> {
+    decode_bytes(&from)
 }
 
-impl Box{
-    fn create_new_box() -> Self {
-        Self { dimentions: 4.5, weight: 5.0, color: BoxColor::Black }
-    }
+///
+/// Lenient version of [`decode_bytes`] that ignores ASCII whitespace
+/// (spaces, tabs, CR and LF) anywhere in the input.
+///
+/// An input made only of whitespace decodes to an empty `Vec`, the same
+/// as an empty string does.
+///
+pub fn decode_tolerant(from: &str) -> Result<Vec<u8>, DecodeError> {
+    let compact: String = from.chars()
+        .filter(|character| !character.is_ascii_whitespace())
+        .collect();
+
+    decode_bytes(&compact)
+}
+
+///
+/// Reports whether [`decode_bytes`] would succeed on `from`: standard
+/// alphabet, canonical padding and a valid length.
+///
+/// Nothing is allocated: the input is decoded piece by piece into a
+/// small stack buffer and the output thrown away. An empty string is
+/// valid.
+///
+/// ## Example
+/// ```
+/// assert!(ancryptor::is_valid_base64("aGVsbG9fd29ybGRfZnJvbV9ydXN0"));
+/// assert!(!ancryptor::is_valid_base64("dfoiuerw892"));
+/// ```
+pub fn is_valid_base64(from: &str) -> bool {
+    is_valid_for(&base64Engine, strip_bom(from))
+}
+
+///
+/// Url-safe version of [`is_valid_base64`]: reports whether
+/// [`decode_url_safe`] would decode `from`, leaving aside whether the
+/// result is valid UTF-8.
+///
+/// ## Example
+/// ```
+/// assert!(ancryptor::is_valid_base64_url_safe("Pz8-Pz8_"));
+/// assert!(!ancryptor::is_valid_base64_url_safe("Pz8+Pz8/"));
+/// ```
+pub fn is_valid_base64_url_safe(from: &str) -> bool {
+    is_valid_for(&base64UrlSafeEngine, strip_bom(from))
+}
+
+///
+/// Compares two secrets, e.g. a decoded token and the value it is
+/// expected to be, in time that does not depend on their contents.
+///
+/// Use this instead of `==` whenever one side is secret: `==` stops at
+/// the first differing byte, which lets an attacker who can time the
+/// comparison guess the secret byte by byte. Only the lengths are not
+/// hidden: inputs of different lengths return `false` straight away.
+///
+/// ## Example
+/// ```
+/// let decoded = ancryptor::decode_bytes("c2VjcmV0").unwrap();
+///
+/// assert!(ancryptor::constant_time_eq(&decoded, b"secret"));
+/// assert!(!ancryptor::constant_time_eq(&decoded, b"Secret"));
+/// ```
+pub fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.ct_eq(b).into()
 }
 
-impl BoxColor{
-    fn print(&self) {
-        match self {
-            BoxColor::Black => println!("Black"),
-            BoxColor::Red => println!("Red"),
-            BoxColor::Blue => println!("Blue"),
+///
+/// Decodes `from` with `engine` one chunk at a time, so validation needs
+/// no more than a fixed-size buffer whatever the input length.
+///
+fn is_valid_for(engine: &impl Engine, from: &str) -> bool {
+    let mut buffer = [0; VALIDATION_CHUNK_LEN / 4 * 3];
+    let mut chunks = from.as_bytes().chunks(VALIDATION_CHUNK_LEN).peekable();
+
+    while let Some(chunk) = chunks.next() {
+        // Each chunk alone may end in padding, the whole input only at its end.
+        let padded_too_early = chunks.peek().is_some() && chunk.ends_with(b"=");
+
+        if padded_too_early || engine.decode_slice(chunk, &mut buffer).is_err() {
+            return false;
         }
     }
-}
 
-fn main(){
+    true
+}
 
-    let boxChar = Box::create_new_box();
-    println!("Box dimension is {:?}- weight is {:?}- color is {:?}", boxChar.dimentions, boxChar.weight, boxChar.color.print())
-}
\ No newline at end of file
+///
+/// Removes a leading UTF-8 byte-order mark, which is never valid base64.
+///
+fn strip_bom(from: &str) -> &str {
+    from.strip_prefix(UTF8_BOM).unwrap_or(from)
+}
@@ -0,0 +1,93 @@
+// @See HMAC-SHA256 and its test vectors:
+//  - https://www.rfc-editor.org/rfc/rfc2104
+//  - https://www.rfc-editor.org/rfc/rfc4231
+
+use alloc::string::String;
+
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+
+type HmacSha256 = Hmac<Sha256>;
+
+///
+/// Authenticates `message` with the shared secret `key` and returns the
+/// HMAC-SHA256 tag as standard base64.
+///
+/// Send the tag along with the message; the receiver checks it with
+/// [`verify`]. Only available with the `hmac` feature.
+///
+/// ## Example
+/// ```
+/// let tag = ancryptor::sign(b"what do ya want for nothing?", b"Jefe");
+///
+/// assert_eq!(tag, "W9zBRr9gdU5qBCQmCJV1x1oAPwidJzmDnexYuWTsOEM=");
+/// ```
+pub fn sign(message: &[u8], key: &[u8]) -> String {
+    crate::encode_bytes(&tag_for(message, key))
+}
+
+///
+/// Checks that `tag_b64` is the [`sign`] tag of `message` under `key`.
+///
+/// The tag is recomputed and compared with [`crate::constant_time_eq`].
+/// Returns `false` if the message or tag were changed, `key` is wrong,
+/// or `tag_b64` is not base64. Only available with the `hmac` feature.
+///
+/// ## Example
+/// ```
+/// let tag = ancryptor::sign(b"amount=10", b"secret");
+///
+/// assert!(ancryptor::verify(b"amount=10", b"secret", &tag));
+/// assert!(!ancryptor::verify(b"amount=99", b"secret", &tag));
+/// ```
+pub fn verify(message: &[u8], key: &[u8], tag_b64: &str) -> bool {
+    match crate::decode_bytes(tag_b64) {
+        Ok(tag) => crate::constant_time_eq(&tag, &tag_for(message, key)),
+        Err(_) => false,
+    }
+}
+
+fn tag_for(message: &[u8], key: &[u8]) -> [u8; 32] {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(message);
+    mac.finalize().into_bytes().into()
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // RFC 4231, test case 2.
+    static KEY: &[u8] = b"Jefe";
+    static MESSAGE: &[u8] = b"what do ya want for nothing?";
+    static TAG: &str = "W9zBRr9gdU5qBCQmCJV1x1oAPwidJzmDnexYuWTsOEM=";
+
+    #[test]
+    fn test_sign_known_vector() {
+        assert_eq!(sign(MESSAGE, KEY), TAG);
+        assert_eq!(
+            crate::encode_hex(&crate::decode_bytes(TAG).unwrap()),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843",
+        );
+    }
+
+    #[test]
+    fn test_verify_accepts_known_vector() {
+        assert!(verify(MESSAGE, KEY, TAG));
+    }
+
+    #[test]
+    fn test_verify_rejects_tampered_message() {
+        assert!(!verify(b"what do ya want for nothing!", KEY, TAG));
+    }
+
+    #[test]
+    fn test_verify_rejects_wrong_key_and_bad_tags() {
+        assert!(!verify(MESSAGE, b"jefe", TAG));
+        assert!(!verify(MESSAGE, KEY, "not base64!"));
+        assert!(!verify(MESSAGE, KEY, &TAG[..TAG.len() - 4]));
+    }
+}
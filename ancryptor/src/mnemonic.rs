@@ -0,0 +1,179 @@
+// @See BIP-39 (Mnemonic code for generating deterministic keys):
+//  - https://github.com/bitcoin/bips/blob/master/bip-0039.mediawiki
+
+use std::sync::OnceLock;
+
+use sha2::{Digest, Sha256};
+
+use crate::error::AncryptorError;
+
+// The official BIP-39 English word list, sorted, one word per line.
+static WORDLIST: &str = include_str!("bip39_english.txt");
+
+// Each word encodes 11 bits: an index into the 2048-word list.
+const BITS_PER_WORD: usize = 11;
+
+// BIP-39 accepts 128 to 256 bits of data, in steps of 32 bits.
+const MIN_DATA_LEN: usize = 16;
+const MAX_DATA_LEN: usize = 32;
+
+///
+/// Encodes `data` as a BIP-39 mnemonic: a list of English words that is
+/// easy to read aloud or write down, e.g. to back up a key.
+///
+/// `data` must be 16, 20, 24, 28 or 32 bytes long, giving 12 to 24 words.
+/// The last word includes a checksum, so [`decode_mnemonic`] can catch a
+/// misheard or mistyped word.
+///
+/// ## Example
+/// ```
+/// let words = ancryptor::encode_mnemonic(&[0x7F; 16]).unwrap();
+///
+/// assert_eq!(words.join(" "), "legal winner thank year wave sausage worth useful legal winner thank yellow");
+/// ```
+pub fn encode_mnemonic(data: &[u8]) -> Result<Vec<String>, AncryptorError> {
+    if !(MIN_DATA_LEN..=MAX_DATA_LEN).contains(&data.len()) || !data.len().is_multiple_of(4) {
+        return Err(AncryptorError::InvalidArgument(
+            format!("mnemonic data must be 16, 20, 24, 28 or 32 bytes, got {}", data.len()),
+        ));
+    }
+
+    // One checksum bit per 32 bits of data, taken from the SHA-256 digest.
+    let checksum_bits = data.len() * 8 / 32;
+    let mut bits = data.to_vec();
+    bits.push(Sha256::digest(data)[0]);
+
+    let word_count = (data.len() * 8 + checksum_bits) / BITS_PER_WORD;
+    let words = wordlist();
+
+    Ok((0..word_count)
+        .map(|word| words[read_bits(&bits, word * BITS_PER_WORD, BITS_PER_WORD)].to_owned())
+        .collect())
+}
+
+///
+/// Decodes a mnemonic produced by [`encode_mnemonic`] back into its data.
+///
+/// Words are matched case-insensitively. Returns
+/// [`AncryptorError::InvalidArgument`] for a word count other than 12,
+/// 15, 18, 21 or 24 or for a word not on the list, and
+/// [`AncryptorError::ChecksumMismatch`] if the checksum does not match.
+///
+pub fn decode_mnemonic(words: &[&str]) -> Result<Vec<u8>, AncryptorError> {
+    let total_bits = words.len() * BITS_PER_WORD;
+    // The data makes up 32 of every 33 bits, the checksum the rest.
+    let data_len = total_bits * 32 / 33 / 8;
+
+    if !(MIN_DATA_LEN..=MAX_DATA_LEN).contains(&data_len) || !words.len().is_multiple_of(3) {
+        return Err(AncryptorError::InvalidArgument(
+            format!("a mnemonic has 12, 15, 18, 21 or 24 words, got {}", words.len()),
+        ));
+    }
+
+    let wordlist = wordlist();
+    let mut bits = vec![0u8; total_bits.div_ceil(8)];
+
+    for (position, word) in words.iter().enumerate() {
+        let index = wordlist.binary_search(&word.to_ascii_lowercase().as_str())
+            .map_err(|_| AncryptorError::InvalidArgument(format!("'{}' is not a mnemonic word", word)))?;
+
+        write_bits(&mut bits, position * BITS_PER_WORD, BITS_PER_WORD, index);
+    }
+
+    let data = bits[..data_len].to_vec();
+    let checksum_bits = total_bits - data_len * 8;
+
+    if read_bits(&bits, data_len * 8, checksum_bits) != read_bits(&Sha256::digest(&data), 0, checksum_bits) {
+        return Err(AncryptorError::ChecksumMismatch);
+    }
+
+    Ok(data)
+}
+
+fn wordlist() -> &'static [&'static str] {
+    static WORDS: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+    WORDS.get_or_init(|| WORDLIST.lines().collect())
+}
+
+///
+/// Reads `count` bits (at most `usize::BITS`) starting at bit `start`,
+/// most significant bit first.
+///
+fn read_bits(bytes: &[u8], start: usize, count: usize) -> usize {
+    (start..start + count).fold(0, |value, bit| {
+        let is_set = bytes[bit / 8] & (0x80 >> (bit % 8)) != 0;
+        (value << 1) | is_set as usize
+    })
+}
+
+fn write_bits(bytes: &mut [u8], start: usize, count: usize, value: usize) {
+    for offset in 0..count {
+        if value & (1 << (count - 1 - offset)) != 0 {
+            let bit = start + offset;
+            bytes[bit / 8] |= 0x80 >> (bit % 8);
+        }
+    }
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_wordlist_is_the_sorted_bip39_list() {
+        let words = wordlist();
+
+        assert_eq!(words.len(), 2048);
+        assert_eq!((words[0], words[2047]), ("abandon", "zoo"));
+        assert!(words.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[test]
+    fn test_mnemonic_round_trip_16_byte_key() {
+        let key: Vec<u8> = (0..16).map(|byte| byte * 17).collect();
+        let words = encode_mnemonic(&key).unwrap();
+        let words: Vec<&str> = words.iter().map(String::as_str).collect();
+
+        assert_eq!(words.len(), 12);
+        assert_eq!(decode_mnemonic(&words).unwrap(), key);
+    }
+
+    #[test]
+    fn test_mnemonic_matches_bip39_vectors() {
+        let vectors: [(&[u8], &str); 3] = [
+            (&[0x00; 16], "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"),
+            (&[0xFF; 16], "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong"),
+            (&[0x00; 32], "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon \
+                           abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon art"),
+        ];
+
+        for (data, mnemonic) in vectors {
+            let words: Vec<&str> = mnemonic.split_whitespace().collect();
+
+            assert_eq!(encode_mnemonic(data).unwrap(), words);
+            assert_eq!(decode_mnemonic(&words).unwrap(), data);
+        }
+    }
+
+    #[test]
+    fn test_mnemonic_rejects_unaligned_lengths() {
+        for len in [0, 4, 15, 17, 36] {
+            assert!(matches!(encode_mnemonic(&vec![1; len]), Err(AncryptorError::InvalidArgument(_))), "{} bytes", len);
+        }
+        assert!(matches!(decode_mnemonic(&["abandon"; 11]), Err(AncryptorError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_mnemonic_detects_wrong_word() {
+        let mut words = vec!["abandon"; 12];
+        // "about" is the only valid last word for all-zero data.
+        words[11] = "abandon";
+
+        assert_eq!(decode_mnemonic(&words), Err(AncryptorError::ChecksumMismatch));
+        assert!(matches!(decode_mnemonic(&["notaword"; 12]), Err(AncryptorError::InvalidArgument(_))));
+    }
+}
@@ -0,0 +1,88 @@
+use percent_encoding::percent_decode_str;
+
+use crate::encoding::{decode_as, EncodingKind};
+use crate::error::AncryptorError;
+
+///
+/// Finds the query parameter `param` in `url`, percent-decodes its value
+/// and then decodes it according to `kind`.
+///
+/// A `+` in the value is kept as-is rather than turned into a space, so
+/// standard base64 that was not percent-encoded still decodes.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{decode_query_param, EncodingKind};
+///
+/// let url = "https://example.com/hook?payload=aGk%3D";
+/// let payload = decode_query_param(url, "payload", EncodingKind::Base64).unwrap();
+///
+/// assert_eq!(payload, b"hi");
+/// ```
+pub fn decode_query_param(url: &str, param: &str, kind: EncodingKind) -> Result<Vec<u8>, AncryptorError> {
+    let value = query_param(url, param).ok_or_else(|| {
+        AncryptorError::MissingParam(param.to_owned())
+    })?;
+
+    Ok(decode_as(&value, kind)?)
+}
+
+///
+/// Returns the percent-decoded value of the first `param` in the
+/// query string of `url`, if present.
+///
+fn query_param(url: &str, param: &str) -> Option<String> {
+    let without_fragment = url.split('#').next().unwrap_or_default();
+    let (_, query) = without_fragment.split_once('?')?;
+
+    query.split('&').find_map(|pair| {
+        let (name, value) = pair.split_once('=').unwrap_or((pair, ""));
+
+        if percent_decode_str(name).decode_utf8_lossy() == param {
+            Some(percent_decode_str(value).decode_utf8_lossy().into_owned())
+        } else {
+            None
+        }
+    })
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_url_safe_query_param() {
+        let url = "https://example.com/hook?id=7&payload=-_8%3D&sig=abc#top";
+        let payload = decode_query_param(url, "payload", EncodingKind::Base64UrlSafe);
+
+        assert_eq!(payload, Ok(vec![0xFB, 0xFF]));
+    }
+
+    #[test]
+    fn test_decode_query_param_missing() {
+        let url = "https://example.com/hook?id=7";
+        let payload = decode_query_param(url, "payload", EncodingKind::Base64UrlSafe);
+
+        assert_eq!(payload, Err(AncryptorError::MissingParam("payload".to_owned())));
+    }
+
+    #[test]
+    fn test_decode_query_param_without_query_string() {
+        let payload = decode_query_param("https://example.com/hook", "payload", EncodingKind::Hex);
+
+        assert_eq!(payload, Err(AncryptorError::MissingParam("payload".to_owned())));
+    }
+
+    #[test]
+    fn test_decode_query_param_invalid_value() {
+        let url = "https://example.com/hook?payload=zz";
+
+        assert!(matches!(
+            decode_query_param(url, "payload", EncodingKind::Hex),
+            Err(AncryptorError::Decode(_)),
+        ));
+    }
+}
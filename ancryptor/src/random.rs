@@ -0,0 +1,144 @@
+// @See rand_core and the operating system RNG:
+//  - https://docs.rs/rand_core/0.6/rand_core/trait.RngCore.html
+//  - https://docs.rs/getrandom/latest/getrandom/
+
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use base64::{
+    Engine as _,
+    engine::general_purpose::URL_SAFE_NO_PAD as base64UrlSafeNoPadEngine,
+};
+
+use crate::error::CryptoError;
+
+// Sandboxed environments can fail to produce entropy for a moment, e.g.
+// right after boot, so a failed read is retried a few times first.
+static RNG_ATTEMPTS: usize = 3;
+
+///
+/// Generates a random 256-bit key from the operating system RNG, suitable
+/// for [`crate::Envelope::seal`] and the other AES-256 functions.
+///
+/// Returns [`CryptoError::EntropyUnavailable`] if the RNG keeps failing.
+///
+/// ## Example
+/// ```
+/// let key = ancryptor::generate_key().unwrap();
+///
+/// assert_ne!(key, ancryptor::generate_key().unwrap());
+/// ```
+pub fn generate_key() -> Result<[u8; 32], CryptoError> {
+    generate_key_with(&mut OsRng)
+}
+
+///
+/// Generates `len` random bytes and returns them as unpadded url-safe
+/// base64, e.g. for session or reset tokens.
+///
+/// Returns [`CryptoError::EntropyUnavailable`] if the RNG keeps failing.
+///
+/// ## Example
+/// ```
+/// let token = ancryptor::random_token(24).unwrap();
+///
+/// assert_eq!(token.len(), 32);
+/// ```
+pub fn random_token(len: usize) -> Result<String, CryptoError> {
+    random_token_with(&mut OsRng, len)
+}
+
+fn generate_key_with<R: RngCore>(rng: &mut R) -> Result<[u8; 32], CryptoError> {
+    let mut key = [0; 32];
+    fill_with_retry(rng, &mut key)?;
+
+    Ok(key)
+}
+
+fn random_token_with<R: RngCore>(rng: &mut R, len: usize) -> Result<String, CryptoError> {
+    let mut bytes = vec![0; len];
+    fill_with_retry(rng, &mut bytes)?;
+
+    Ok(base64UrlSafeNoPadEngine.encode(bytes))
+}
+
+fn fill_with_retry<R: RngCore>(rng: &mut R, buffer: &mut [u8]) -> Result<(), CryptoError> {
+    for _ in 0..RNG_ATTEMPTS {
+        if rng.try_fill_bytes(buffer).is_ok() {
+            return Ok(());
+        }
+    }
+
+    Err(CryptoError::EntropyUnavailable)
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aes_gcm::aead::rand_core;
+    use std::num::NonZeroU32;
+
+    // Fails the first `failures` reads, then yields `0xAB` bytes.
+    struct FlakyRng {
+        failures: usize,
+        attempts: usize,
+    }
+
+    impl RngCore for FlakyRng {
+        fn next_u32(&mut self) -> u32 {
+            rand_core::impls::next_u32_via_fill(self)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            rand_core::impls::next_u64_via_fill(self)
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            self.try_fill_bytes(dest).unwrap()
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand_core::Error> {
+            self.attempts += 1;
+
+            if self.attempts <= self.failures {
+                return Err(NonZeroU32::new(rand_core::Error::CUSTOM_START).unwrap().into());
+            }
+
+            dest.fill(0xAB);
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_always_failing_rng_returns_clean_error() {
+        let mut rng = FlakyRng { failures: usize::MAX, attempts: 0 };
+
+        assert_eq!(generate_key_with(&mut rng), Err(CryptoError::EntropyUnavailable));
+        assert_eq!(rng.attempts, RNG_ATTEMPTS);
+        assert_eq!(random_token_with(&mut rng, 16), Err(CryptoError::EntropyUnavailable));
+    }
+
+    #[test]
+    fn test_transient_failure_is_retried() {
+        let mut rng = FlakyRng { failures: RNG_ATTEMPTS - 1, attempts: 0 };
+
+        assert_eq!(generate_key_with(&mut rng), Ok([0xAB; 32]));
+    }
+
+    #[test]
+    fn test_flaky_rng_words_come_from_its_bytes() {
+        let mut rng = FlakyRng { failures: 0, attempts: 0 };
+
+        assert_eq!(rng.next_u32(), 0xABAB_ABAB);
+        assert_eq!(rng.next_u64(), 0xABAB_ABAB_ABAB_ABAB);
+    }
+
+    #[test]
+    fn test_random_token_is_url_safe() {
+        let token = random_token(48).unwrap();
+
+        assert_eq!(token.len(), 64);
+        assert!(token.chars().all(|character| character.is_ascii_alphanumeric() || character == '-' || character == '_'));
+    }
+}
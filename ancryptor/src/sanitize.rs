@@ -0,0 +1,289 @@
+use crate::error::AncryptorError;
+
+///
+/// Decodes standard base64 that went through an over-eager HTML
+/// sanitizer, e.g. `+` turned into `&#43;`.
+///
+/// Numeric entities (`&#43;`, `&#x2b;`) and the common named ones (`&amp;`,
+/// `&lt;`, `&gt;`, `&quot;`, `&apos;`) are turned back into characters
+/// before decoding. Anything else is left as-is.
+///
+/// ## Example
+/// ```
+/// let decoded = ancryptor::decode_html_unescaped("&#43;/8=").unwrap();
+///
+/// assert_eq!(decoded, vec![0xFB, 0xFF]);
+/// ```
+pub fn decode_html_unescaped(s: &str) -> Result<Vec<u8>, AncryptorError> {
+    Ok(crate::decode_bytes(&unescape_html(s))?)
+}
+
+///
+/// Decodes base64 that is annotated with comment lines, such as a
+/// `# generated by ...` header, by dropping every line that starts with
+/// `comment_prefix` (after any indentation) and decoding the rest.
+///
+/// The remaining lines are joined back together, so a payload may be
+/// split across several lines.
+///
+/// ## Example
+/// ```
+/// let annotated = "# greeting\naGVsbG9f\n# still a greeting\nd29ybGQ=\n";
+///
+/// assert_eq!(ancryptor::decode_skip_comments(annotated, "#").unwrap(), b"hello_world");
+/// ```
+pub fn decode_skip_comments(s: &str, comment_prefix: &str) -> Result<Vec<u8>, AncryptorError> {
+    if comment_prefix.is_empty() {
+        return Err(AncryptorError::InvalidArgument("comment prefix must not be empty".to_owned()));
+    }
+
+    let payload: String = s.lines()
+        .filter(|line| !line.trim_start().starts_with(comment_prefix))
+        .collect();
+
+    Ok(crate::decode_tolerant(&payload)?)
+}
+
+///
+/// Decodes base64 exactly the way GNU coreutils `base64 -d` (checked
+/// against version 9.1) does, returning the bytes it would write to
+/// stdout, for callers migrating away from shelling out to it.
+///
+/// The rules, all of them stricter or looser than [`crate::decode_bytes`]:
+///
+/// - `\n` is skipped anywhere. Every other character outside the
+///   standard alphabet, including spaces, tabs and `\r`, is invalid.
+/// - Input is read in groups of four characters. `xx==` and `xxx=` end a
+///   group, and a new group may follow, so `aGk=aGk=` decodes to `hihi`.
+/// - Unused low bits in the last character of a group are ignored, so
+///   `aGF=` and `aGE=` both decode to `ha`.
+/// - Bytes are produced as soon as enough characters have been read.
+///   GNU stops at the first invalid character or misplaced `=` and exits
+///   with an error, but what it decoded up to there has already been
+///   written; this function returns those same bytes. A group left
+///   incomplete at the end of the input behaves the same way.
+///
+/// Use [`gnu_decode_is_valid`] to tell whether `base64 -d` would have
+/// exited with an error.
+///
+/// ## Example
+/// ```
+/// assert_eq!(ancryptor::decode_gnu_compatible("aGVs\nbG8="), b"hello");
+/// // GNU prints "hel" for this, then complains about the space.
+/// assert_eq!(ancryptor::decode_gnu_compatible("aGVs bG8="), b"hel");
+/// ```
+pub fn decode_gnu_compatible(s: &str) -> Vec<u8> {
+    gnu_decode(s).0
+}
+
+///
+/// Returns whether GNU `base64 -d` accepts `s` without an error, i.e.
+/// whether [`decode_gnu_compatible`] decoded all of it.
+///
+pub fn gnu_decode_is_valid(s: &str) -> bool {
+    gnu_decode(s).1
+}
+
+///
+/// Returns the bytes GNU `base64 -d` writes for `s` and whether it would
+/// succeed.
+///
+fn gnu_decode(s: &str) -> (Vec<u8>, bool) {
+    let mut decoded = Vec::with_capacity(s.len() / 4 * 3);
+    let mut group = [0u32; 4];
+    let mut group_len = 0;
+    // Set after `xx=`, when only a second `=` may follow.
+    let mut awaiting_padding = false;
+
+    for byte in s.bytes() {
+        if byte == b'\n' {
+            continue;
+        }
+
+        if awaiting_padding {
+            if byte != b'=' {
+                return (decoded, false);
+            }
+            awaiting_padding = false;
+            group_len = 0;
+            continue;
+        }
+
+        if byte == b'=' {
+            match group_len {
+                2 => awaiting_padding = true,
+                3 => group_len = 0,
+                _ => return (decoded, false),
+            }
+            continue;
+        }
+
+        let Some(value) = base64_value(byte) else {
+            return (decoded, false);
+        };
+        group[group_len] = value;
+        group_len += 1;
+
+        match group_len {
+            2 => decoded.push(((group[0] << 2) | (group[1] >> 4)) as u8),
+            3 => decoded.push(((group[1] << 4) | (group[2] >> 2)) as u8),
+            4 => {
+                decoded.push(((group[2] << 6) | group[3]) as u8);
+                group_len = 0;
+            }
+            _ => {}
+        }
+    }
+
+    (decoded, group_len == 0 && !awaiting_padding)
+}
+
+fn base64_value(byte: u8) -> Option<u32> {
+    let value = match byte {
+        b'A'..=b'Z' => byte - b'A',
+        b'a'..=b'z' => byte - b'a' + 26,
+        b'0'..=b'9' => byte - b'0' + 52,
+        b'+' => 62,
+        b'/' => 63,
+        _ => return None,
+    };
+
+    Some(value as u32)
+}
+
+fn unescape_html(s: &str) -> String {
+    let mut unescaped = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        unescaped.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let entity = rest.find(';').and_then(|end| {
+            entity_char(&rest[1..end]).map(|character| (character, end))
+        });
+
+        match entity {
+            Some((character, end)) => {
+                unescaped.push(character);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                unescaped.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    unescaped.push_str(rest);
+    unescaped
+}
+
+///
+/// Resolves the text between `&` and `;`, e.g. `amp`, `#43` or `#x2b`.
+///
+fn entity_char(entity: &str) -> Option<char> {
+    let code_point = match entity {
+        "amp" => return Some('&'),
+        "lt" => return Some('<'),
+        "gt" => return Some('>'),
+        "quot" => return Some('"'),
+        "apos" => return Some('\''),
+        _ => match entity.strip_prefix('#')? {
+            hex if hex.starts_with(['x', 'X']) => u32::from_str_radix(&hex[1..], 16).ok()?,
+            decimal => decimal.parse().ok()?,
+        },
+    };
+
+    char::from_u32(code_point)
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode_html_unescaped_plus_entity_forms() {
+        // "+/8=" is the standard base64 of [0xFB, 0xFF].
+        for escaped in ["&#43;/8=", "&#x2b;/8=", "&#X2B;/8=", "&#43;&#47;8&#61;"] {
+            assert_eq!(decode_html_unescaped(escaped).unwrap(), vec![0xFB, 0xFF], "{}", escaped);
+        }
+    }
+
+    #[test]
+    fn test_unescape_html_named_entities() {
+        assert_eq!(unescape_html("a&amp;b&lt;c&gt;&quot;&apos;"), "a&b<c>\"'");
+    }
+
+    #[test]
+    fn test_unescape_html_leaves_unknown_entities() {
+        assert_eq!(unescape_html("&nbsp; & &#zz; &#43"), "&nbsp; & &#zz; &#43");
+    }
+
+    #[test]
+    fn test_decode_html_unescaped_plain_base64() {
+        assert_eq!(decode_html_unescaped("aGk=").unwrap(), b"hi");
+    }
+
+    #[test]
+    fn test_decode_skip_comments_interleaved() {
+        let annotated = "# exported by the backup tool\n\
+                         aGVsbG9fd29y\n\
+                         # format: base64\n\
+                         bGRfZnJvbV9ydXN0\n";
+
+        assert_eq!(decode_skip_comments(annotated, "#").unwrap(), b"hello_world_from_rust");
+    }
+
+    #[test]
+    fn test_decode_skip_comments_custom_prefix() {
+        let annotated = "// header\r\naGk=\r\n";
+
+        assert_eq!(decode_skip_comments(annotated, "//").unwrap(), b"hi");
+    }
+
+    #[test]
+    fn test_decode_skip_comments_rejects_empty_prefix() {
+        assert!(matches!(decode_skip_comments("aGk=", ""), Err(AncryptorError::InvalidArgument(_))));
+    }
+
+    #[test]
+    fn test_gnu_compatible_matches_gnu_base64_output() {
+        // Outputs recorded from `printf '%s' <input> | base64 -d`, GNU coreutils 9.1.
+        let cases: [(&str, &[u8], bool); 16] = [
+            ("aGVsbG8=", b"hello", true),
+            ("aGVs\nbG8=", b"hello", true),
+            ("\naGk=\n\n", b"hi", true),
+            ("aGVsbG8=aGk=", b"hellohi", true),
+            ("aGVsbG9=", b"hello", true),
+            ("aGF=", b"ha", true),
+            ("", b"", true),
+            ("aGVs bG8=", b"hel", false),
+            ("aGVs\r\nbG8=", b"hel", false),
+            ("\taGVsbG8=", b"", false),
+            ("aGVsbG8", b"hello", false),
+            ("aGVsbG8==", b"hello", false),
+            ("aGk=x", b"hi", false),
+            ("aG=k", b"h", false),
+            ("abc", &[0x69, 0xB7], false),
+            ("-_8=", b"", false),
+        ];
+
+        for (input, expected, valid) in cases {
+            assert_eq!(decode_gnu_compatible(input), expected, "{:?}", input);
+            assert_eq!(gnu_decode_is_valid(input), valid, "{:?}", input);
+        }
+    }
+
+    #[test]
+    fn test_gnu_compatible_agrees_with_strict_decoder_on_canonical_input() {
+        let data: Vec<u8> = (0..=255).collect();
+        let encoded = crate::encode_bytes(&data);
+
+        assert_eq!(decode_gnu_compatible(&encoded), data);
+        assert!(gnu_decode_is_valid(&encoded));
+    }
+}
@@ -0,0 +1,388 @@
+use std::io::{self, Read, Write};
+
+use crate::encoding::{decode_as, encode_as, encoded_len, EncodingKind};
+use crate::error::AncryptorError;
+
+// How much is read from the source per iteration.
+static READ_CHUNK_SIZE: usize = 64 * 1024;
+
+// A multiple of 3, so every chunk encodes without padding.
+static ENCODE_CHUNK_SIZE: usize = 48 * 1024;
+
+///
+/// Number of encoded characters that decode to a whole number of bytes.
+///
+fn text_group_len(kind: EncodingKind) -> usize {
+    match kind {
+        EncodingKind::Base64 | EncodingKind::Base64UrlSafe => 4,
+        EncodingKind::Base32 => 8,
+        EncodingKind::Hex => 2,
+    }
+}
+
+///
+/// Number of raw bytes that encode without padding.
+///
+fn byte_group_len(kind: EncodingKind) -> usize {
+    match kind {
+        EncodingKind::Base64 | EncodingKind::Base64UrlSafe => 3,
+        EncodingKind::Base32 => 5,
+        EncodingKind::Hex => 1,
+    }
+}
+
+///
+/// Re-encodes text from one format to another while streaming, so
+/// neither the source nor the result has to fit in memory.
+///
+/// Whitespace in the source (such as the line breaks of a hex dump) is
+/// ignored. Decoding and encoding only ever happen on whole groups - 2
+/// characters for hex, 8 for base32, 4 for base64 on the way in, and
+/// 1, 5 and 3 bytes on the way out - with leftovers carried over to the
+/// next chunk, so the output is identical to encoding everything at once.
+///
+/// Returns the number of bytes written. Invalid source text fails with
+/// an [`io::ErrorKind::InvalidData`] error.
+///
+/// ## Example
+/// ```
+/// use ancryptor::{transcode_reader, EncodingKind};
+///
+/// let mut base64 = Vec::new();
+/// transcode_reader("6869\n".as_bytes(), &mut base64, EncodingKind::Hex, EncodingKind::Base64).unwrap();
+///
+/// assert_eq!(base64, b"aGk=");
+/// ```
+pub fn transcode_reader<R: Read, W: Write>(
+    mut reader: R,
+    mut writer: W,
+    from: EncodingKind,
+    to: EncodingKind,
+) -> io::Result<u64> {
+    let text_group = text_group_len(from);
+    let byte_group = byte_group_len(to);
+
+    let mut chunk = vec![0; READ_CHUNK_SIZE];
+    let mut pending_text: Vec<u8> = Vec::new();
+    let mut pending_bytes: Vec<u8> = Vec::new();
+    let mut padded = false;
+    let mut written = 0;
+
+    loop {
+        let read = read_retrying(&mut reader, &mut chunk)?;
+        let finished = read == 0;
+
+        pending_text.extend(chunk[..read].iter().filter(|byte| !byte.is_ascii_whitespace()));
+
+        let text_len = if finished {
+            pending_text.len()
+        } else {
+            pending_text.len() - pending_text.len() % text_group
+        };
+        pending_bytes.extend(decode_groups(&pending_text[..text_len], from, &mut padded)?);
+        pending_text.drain(..text_len);
+
+        let bytes_len = if finished {
+            pending_bytes.len()
+        } else {
+            pending_bytes.len() - pending_bytes.len() % byte_group
+        };
+        let encoded = encode_as(&pending_bytes[..bytes_len], to);
+        writer.write_all(encoded.as_bytes())?;
+        written += encoded.len() as u64;
+        pending_bytes.drain(..bytes_len);
+
+        if finished {
+            writer.flush()?;
+            return Ok(written);
+        }
+    }
+}
+
+///
+/// Encodes everything read from `reader` as standard, padded base64 and
+/// writes it to `writer`, 48 KiB at a time, so files of any size can be
+/// encoded in constant memory.
+///
+/// The output is identical to [`crate::encode_bytes`] on the whole
+/// input. Returns the number of bytes written.
+///
+/// ## Example
+/// ```
+/// let mut encoded = Vec::new();
+/// let written = ancryptor::encode_stream("hello".as_bytes(), &mut encoded).unwrap();
+///
+/// assert_eq!(encoded, b"aGVsbG8=");
+/// assert_eq!(written, 8);
+/// ```
+pub fn encode_stream<R: Read, W: Write>(mut reader: R, mut writer: W) -> io::Result<u64> {
+    let mut chunk = Vec::with_capacity(ENCODE_CHUNK_SIZE);
+    let mut written = 0;
+
+    loop {
+        chunk.clear();
+        reader.by_ref().take(ENCODE_CHUNK_SIZE as u64).read_to_end(&mut chunk)?;
+
+        let encoded = encode_as(&chunk, EncodingKind::Base64);
+        writer.write_all(encoded.as_bytes())?;
+        written += encoded.len() as u64;
+
+        // Only the last chunk can be short.
+        if chunk.len() < ENCODE_CHUNK_SIZE {
+            writer.flush()?;
+            return Ok(written);
+        }
+    }
+}
+
+///
+/// Decodes standard, padded base64 read from `reader` and writes the raw
+/// bytes to `writer`, in constant memory.
+///
+/// Whitespace, such as line breaks, is ignored. Returns the number of
+/// bytes written; invalid base64 fails with an
+/// [`io::ErrorKind::InvalidData`] error.
+///
+pub fn decode_stream<R: Read, W: Write>(mut reader: R, mut writer: W) -> io::Result<u64> {
+    let text_group = text_group_len(EncodingKind::Base64);
+
+    let mut chunk = vec![0; READ_CHUNK_SIZE];
+    let mut pending_text: Vec<u8> = Vec::new();
+    let mut padded = false;
+    let mut written = 0;
+
+    loop {
+        let read = read_retrying(&mut reader, &mut chunk)?;
+        let finished = read == 0;
+
+        pending_text.extend(chunk[..read].iter().filter(|byte| !byte.is_ascii_whitespace()));
+
+        let text_len = if finished {
+            pending_text.len()
+        } else {
+            pending_text.len() - pending_text.len() % text_group
+        };
+        let decoded = decode_groups(&pending_text[..text_len], EncodingKind::Base64, &mut padded)?;
+        writer.write_all(&decoded)?;
+        written += decoded.len() as u64;
+        pending_text.drain(..text_len);
+
+        if finished {
+            writer.flush()?;
+            return Ok(written);
+        }
+    }
+}
+
+///
+/// Encodes `data` as standard base64 chunk by chunk, calling `progress`
+/// after each chunk with the fraction done so far, from `0.0` to `1.0`,
+/// e.g. to drive a progress bar.
+///
+/// The last call is always `1.0`, even for empty input. The result is
+/// identical to [`crate::encode_bytes`].
+///
+/// ## Example
+/// ```
+/// let mut last_progress = 0.0;
+/// let encoded = ancryptor::encode_with_progress(b"hello", |fraction| last_progress = fraction);
+///
+/// assert_eq!(encoded, "aGVsbG8=");
+/// assert_eq!(last_progress, 1.0);
+/// ```
+pub fn encode_with_progress(data: &[u8], mut progress: impl FnMut(f32)) -> String {
+    if data.is_empty() {
+        progress(1.0);
+        return String::new();
+    }
+
+    let mut encoded = String::with_capacity(encoded_len(data.len(), EncodingKind::Base64));
+    let mut done = 0;
+
+    for chunk in data.chunks(ENCODE_CHUNK_SIZE) {
+        encoded.push_str(&encode_as(chunk, EncodingKind::Base64));
+        done += chunk.len();
+        progress(done as f32 / data.len() as f32);
+    }
+
+    encoded
+}
+
+fn read_retrying<R: Read>(reader: &mut R, buffer: &mut [u8]) -> io::Result<usize> {
+    loop {
+        match reader.read(buffer) {
+            Err(error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            result => return result,
+        }
+    }
+}
+
+///
+/// Decodes whole groups of `text`, which must not follow a padded group:
+/// each read is decoded on its own, so `padded` remembers across reads
+/// that the input should have ended, as `incremental::Decoder` does.
+///
+fn decode_groups(text: &[u8], kind: EncodingKind, padded: &mut bool) -> io::Result<Vec<u8>> {
+    if text.is_empty() {
+        return Ok(Vec::new());
+    }
+    if *padded {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "data after padding"));
+    }
+
+    let decoded = decode_chunk(text, kind)?;
+    *padded = text.ends_with(b"=");
+
+    Ok(decoded)
+}
+
+fn decode_chunk(text: &[u8], kind: EncodingKind) -> io::Result<Vec<u8>> {
+    let text = std::str::from_utf8(text).map_err(|error| io::Error::new(io::ErrorKind::InvalidData, error))?;
+
+    Ok(decode_as(text, kind).map_err(AncryptorError::Decode)?)
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::fs::File;
+    use std::io::BufWriter;
+
+    // Deterministic xorshift bytes, so failures are reproducible.
+    fn pseudo_random_bytes(len: usize) -> Vec<u8> {
+        let mut state: u32 = 0x9E37_79B9;
+
+        (0..len).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state as u8
+        }).collect()
+    }
+
+    #[test]
+    fn test_stream_one_megabyte_round_trip() {
+        let data = pseudo_random_bytes(1024 * 1024);
+
+        let mut encoded = Vec::new();
+        let written = encode_stream(data.as_slice(), &mut encoded).unwrap();
+        assert_eq!(written, encoded.len() as u64);
+        assert_eq!(encoded, crate::encode_bytes(&data).into_bytes());
+
+        let mut decoded = Vec::new();
+        let written = decode_stream(encoded.as_slice(), &mut decoded).unwrap();
+        assert_eq!(written, data.len() as u64);
+        assert_eq!(decoded, data);
+    }
+
+    #[test]
+    fn test_stream_chunk_boundaries() {
+        for len in [0, 1, ENCODE_CHUNK_SIZE - 1, ENCODE_CHUNK_SIZE, ENCODE_CHUNK_SIZE + 1] {
+            let data = pseudo_random_bytes(len);
+            let mut encoded = Vec::new();
+            let mut decoded = Vec::new();
+
+            encode_stream(data.as_slice(), &mut encoded).unwrap();
+            decode_stream(encoded.as_slice(), &mut decoded).unwrap();
+
+            assert_eq!(decoded, data, "length {}", len);
+        }
+    }
+
+    #[test]
+    fn test_decode_stream_ignores_line_breaks_and_rejects_garbage() {
+        let mut decoded = Vec::new();
+        decode_stream("aGVs\nbG8=\n".as_bytes(), &mut decoded).unwrap();
+        assert_eq!(decoded, b"hello");
+
+        let error = decode_stream("dfoiuerw892".as_bytes(), io::sink()).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_decode_stream_rejects_data_after_padding_at_read_boundary() {
+        // The first read ends exactly on a padded group.
+        let mut encoded = "AAAA".repeat(READ_CHUNK_SIZE / 4 - 1);
+        encoded.push_str("aGk=aGk=");
+
+        let error = decode_stream(encoded.as_bytes(), io::sink()).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+        assert!(crate::decode_bytes(&encoded).is_err());
+
+        let error = transcode_reader(encoded.as_bytes(), io::sink(), EncodingKind::Base64, EncodingKind::Hex).unwrap_err();
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_transcode_multi_megabyte_hex_file_to_base64() {
+        let data = pseudo_random_bytes(3 * 1024 * 1024 + 7);
+        let mut hex_file = tempfile::NamedTempFile::new().unwrap();
+
+        {
+            // Written as a hex dump, 32 bytes per line.
+            let mut writer = BufWriter::new(hex_file.as_file_mut());
+            for line in data.chunks(32) {
+                writeln!(writer, "{}", encode_as(line, EncodingKind::Hex)).unwrap();
+            }
+        }
+
+        let mut base64 = Vec::new();
+        let written = transcode_reader(
+            File::open(hex_file.path()).unwrap(),
+            &mut base64,
+            EncodingKind::Hex,
+            EncodingKind::Base64,
+        ).unwrap();
+
+        assert_eq!(written, base64.len() as u64);
+        assert_eq!(String::from_utf8(base64).unwrap(), crate::encode_bytes(&data));
+    }
+
+    #[test]
+    fn test_transcode_base64_to_base32_and_back() {
+        let data = pseudo_random_bytes(200_003);
+        let base64 = crate::encode_bytes(&data);
+
+        let mut base32 = Vec::new();
+        transcode_reader(base64.as_bytes(), &mut base32, EncodingKind::Base64, EncodingKind::Base32).unwrap();
+
+        let mut round_tripped = Vec::new();
+        transcode_reader(base32.as_slice(), &mut round_tripped, EncodingKind::Base32, EncodingKind::Base64).unwrap();
+
+        assert_eq!(String::from_utf8(round_tripped).unwrap(), base64);
+    }
+
+    #[test]
+    fn test_transcode_invalid_source_is_invalid_data() {
+        let error = transcode_reader("zz".as_bytes(), io::sink(), EncodingKind::Hex, EncodingKind::Base64).unwrap_err();
+
+        assert_eq!(error.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn test_encode_with_progress_is_monotonic_and_ends_at_one() {
+        let data = pseudo_random_bytes(1_000_000);
+        let mut reported = Vec::new();
+
+        let encoded = encode_with_progress(&data, |fraction| reported.push(fraction));
+
+        assert_eq!(encoded, crate::encode_bytes(&data));
+        assert!(reported.len() > 1);
+        assert!(reported.windows(2).all(|pair| pair[0] < pair[1]));
+        assert!(reported.iter().all(|fraction| (0.0..=1.0).contains(fraction)));
+        assert_eq!(reported.last(), Some(&1.0));
+    }
+
+    #[test]
+    fn test_encode_with_progress_empty_input() {
+        let mut reported = Vec::new();
+
+        assert_eq!(encode_with_progress(&[], |fraction| reported.push(fraction)), "");
+        assert_eq!(reported, vec![1.0]);
+    }
+}
@@ -0,0 +1,408 @@
+// @See HMAC-SHA256, HKDF and base64url:
+//  - https://www.rfc-editor.org/rfc/rfc2104
+//  - https://www.rfc-editor.org/rfc/rfc5869
+//  - https://www.rfc-editor.org/rfc/rfc4648#section-5
+
+use base64::{
+    Engine as _,
+    engine::general_purpose::URL_SAFE_NO_PAD as base64UrlSafeNoPadEngine,
+};
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use zeroize::Zeroizing;
+
+use crate::error::AncryptorError;
+
+type HmacSha256 = Hmac<Sha256>;
+
+static SIGNATURE_SEPARATOR: char = '.';
+
+// Expiring and timestamped tokens start their data with a time, as
+// big-endian seconds since the Unix epoch.
+const TIMESTAMP_LEN: usize = 8;
+
+///
+/// Expiring and timestamped tokens are signed under their own key,
+/// derived from the caller's one, so that a token of one kind cannot be
+/// passed off as another one signed under the same key. Plain tokens use
+/// the key as it is.
+///
+#[derive(Clone, Copy)]
+enum TokenKind {
+    Signed,
+    Expiring,
+    Timestamped,
+}
+
+impl TokenKind {
+    fn signing_key(self, key: &[u8]) -> Zeroizing<Vec<u8>> {
+        let label: &[u8] = match self {
+            TokenKind::Signed => return Zeroizing::new(key.to_vec()),
+            TokenKind::Expiring => b"ancryptor/expiring",
+            TokenKind::Timestamped => b"ancryptor/timestamped",
+        };
+
+        // HKDF rather than HMAC(key, label): a plain token over `label`
+        // would otherwise be the derived key itself.
+        let mut signing_key = Zeroizing::new(vec![0; 32]);
+        Hkdf::<Sha256>::new(None, key)
+            .expand(label, &mut signing_key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+
+        signing_key
+    }
+}
+
+///
+/// Encodes `data` and signs it with HMAC-SHA256 under `key`, producing
+/// `base64url(data).base64url(hmac)` without padding.
+///
+/// The payload is only encoded, not encrypted: anyone can read it, but
+/// nobody without `key` can change it unnoticed. Use
+/// [`verify_and_decode`] to check and unpack a token.
+///
+/// ## Example
+/// ```
+/// let token = ancryptor::encode_and_sign(b"user=7", b"secret");
+///
+/// assert!(token.starts_with("dXNlcj03."));
+/// assert_eq!(ancryptor::verify_and_decode(&token, b"secret").unwrap(), b"user=7");
+/// ```
+pub fn encode_and_sign(data: &[u8], key: &[u8]) -> String {
+    sign_as(TokenKind::Signed, data, key)
+}
+
+///
+/// Checks the signature of a token produced by [`encode_and_sign`] and
+/// returns its payload.
+///
+/// The signature is compared in constant time. Returns
+/// [`AncryptorError::MalformedToken`] if the token has no `.` separator
+/// or is not base64url, and [`AncryptorError::InvalidSignature`] if the
+/// payload or signature were changed or `key` is wrong. Tokens of
+/// another kind, e.g. from [`encode_expiring`], are
+/// [`AncryptorError::InvalidSignature`] too.
+///
+pub fn verify_and_decode(token: &str, key: &[u8]) -> Result<Vec<u8>, AncryptorError> {
+    verify_as(TokenKind::Signed, token, key)
+}
+
+///
+/// Like [`encode_and_sign`], but the token stops verifying `ttl` from now.
+///
+/// The expiry is stored in front of `data`, inside the signed region, so
+/// it cannot be extended without the key. The token is signed under a
+/// key derived from `key` for expiring tokens only, so it does not verify
+/// as any other kind. Use [`decode_expiring`] to check and unpack it.
+///
+/// ## Example
+/// ```
+/// use std::time::Duration;
+///
+/// let token = ancryptor::encode_expiring(b"reset=7", Duration::from_secs(900), b"secret");
+///
+/// assert_eq!(ancryptor::decode_expiring(&token, b"secret").unwrap(), b"reset=7");
+/// ```
+pub fn encode_expiring(data: &[u8], ttl: Duration, key: &[u8]) -> String {
+    let expires_at = unix_time_now().saturating_add(ttl.as_secs());
+
+    encode_expiring_at(data, expires_at, key)
+}
+
+///
+/// Checks the signature and the expiry of a token produced by
+/// [`encode_expiring`] and returns its payload.
+///
+/// Returns the same errors as [`verify_and_decode`], plus
+/// [`AncryptorError::Expired`] once the expiry has passed. Tokens from
+/// [`encode_and_sign`] or [`encode_timestamped`] are
+/// [`AncryptorError::InvalidSignature`].
+///
+pub fn decode_expiring(token: &str, key: &[u8]) -> Result<Vec<u8>, AncryptorError> {
+    decode_expiring_at(token, key, unix_time_now())
+}
+
+///
+/// Like [`encode_and_sign`], with the current time signed in front of
+/// `data`, so every record carries a tamper-evident creation time, e.g.
+/// for audit logs.
+///
+/// Unlike [`encode_expiring`] nothing is rejected for being old: use
+/// [`decode_timestamped`] to get the verified time back.
+///
+/// ## Example
+/// ```
+/// let token = ancryptor::encode_timestamped(b"login user=7", b"secret");
+/// let (created_at, payload) = ancryptor::decode_timestamped(&token, b"secret").unwrap();
+///
+/// assert!(created_at > 1_600_000_000);
+/// assert_eq!(payload, b"login user=7");
+/// ```
+pub fn encode_timestamped(data: &[u8], key: &[u8]) -> String {
+    sign_with_timestamp(TokenKind::Timestamped, data, unix_time_now(), key)
+}
+
+///
+/// Checks the signature of a token produced by [`encode_timestamped`] and
+/// returns its creation time, in seconds since the Unix epoch, and its
+/// payload.
+///
+/// Returns the same errors as [`verify_and_decode`]. Tokens from
+/// [`encode_and_sign`] or [`encode_expiring`] are
+/// [`AncryptorError::InvalidSignature`], so an expiry is never reported
+/// as a creation time.
+///
+pub fn decode_timestamped(token: &str, key: &[u8]) -> Result<(u64, Vec<u8>), AncryptorError> {
+    verify_with_timestamp(TokenKind::Timestamped, token, key)
+}
+
+fn encode_expiring_at(data: &[u8], expires_at: u64, key: &[u8]) -> String {
+    sign_with_timestamp(TokenKind::Expiring, data, expires_at, key)
+}
+
+fn decode_expiring_at(token: &str, key: &[u8], now: u64) -> Result<Vec<u8>, AncryptorError> {
+    let (expires_at, data) = verify_with_timestamp(TokenKind::Expiring, token, key)?;
+
+    if now >= expires_at {
+        return Err(AncryptorError::Expired);
+    }
+
+    Ok(data)
+}
+
+fn sign_as(kind: TokenKind, data: &[u8], key: &[u8]) -> String {
+    let signature = sign(data, &kind.signing_key(key)).finalize().into_bytes();
+
+    format!(
+        "{}{}{}",
+        base64UrlSafeNoPadEngine.encode(data),
+        SIGNATURE_SEPARATOR,
+        base64UrlSafeNoPadEngine.encode(signature),
+    )
+}
+
+fn verify_as(kind: TokenKind, token: &str, key: &[u8]) -> Result<Vec<u8>, AncryptorError> {
+    let (payload, signature) = token.trim().split_once(SIGNATURE_SEPARATOR)
+        .ok_or(AncryptorError::MalformedToken)?;
+
+    let payload = decode_part(payload)?;
+    let signature = decode_part(signature)?;
+
+    sign(&payload, &kind.signing_key(key))
+        .verify_slice(&signature)
+        .map_err(|_| AncryptorError::InvalidSignature)?;
+
+    Ok(payload)
+}
+
+fn sign_with_timestamp(kind: TokenKind, data: &[u8], timestamp: u64, key: &[u8]) -> String {
+    let mut timestamped = Vec::with_capacity(TIMESTAMP_LEN + data.len());
+    timestamped.extend_from_slice(&timestamp.to_be_bytes());
+    timestamped.extend_from_slice(data);
+
+    sign_as(kind, &timestamped, key)
+}
+
+fn verify_with_timestamp(kind: TokenKind, token: &str, key: &[u8]) -> Result<(u64, Vec<u8>), AncryptorError> {
+    let mut timestamped = verify_as(kind, token, key)?;
+
+    if timestamped.len() < TIMESTAMP_LEN {
+        return Err(AncryptorError::MalformedToken);
+    }
+
+    let data = timestamped.split_off(TIMESTAMP_LEN);
+    Ok((u64::from_be_bytes(timestamped.try_into().unwrap()), data))
+}
+
+fn unix_time_now() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|elapsed| elapsed.as_secs()).unwrap_or(0)
+}
+
+fn sign(data: &[u8], key: &[u8]) -> HmacSha256 {
+    let mut mac = <HmacSha256 as Mac>::new_from_slice(key).expect("HMAC accepts keys of any size");
+    mac.update(data);
+    mac
+}
+
+fn decode_part(part: &str) -> Result<Vec<u8>, AncryptorError> {
+    base64UrlSafeNoPadEngine.decode(part)
+        .map_err(|_| AncryptorError::MalformedToken)
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const KEY: &[u8] = b"token-signing-key";
+
+    #[test]
+    fn test_valid_token_round_trip() {
+        let token = encode_and_sign(b"hello_world_from_rust", KEY);
+
+        assert_eq!(verify_and_decode(&token, KEY).unwrap(), b"hello_world_from_rust");
+    }
+
+    #[test]
+    fn test_expiring_token_is_rejected_as_plain_token() {
+        let token = encode_expiring(b"reset=7", Duration::from_secs(60), KEY);
+
+        assert_eq!(verify_and_decode(&token, KEY), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_hand_built_token_verifies() {
+        let signature = sign(b"user=7", KEY).finalize().into_bytes();
+        let token = format!("{}.{}", base64UrlSafeNoPadEngine.encode(b"user=7"), base64UrlSafeNoPadEngine.encode(signature));
+
+        assert_eq!(token, encode_and_sign(b"user=7", KEY));
+        assert_eq!(verify_and_decode(&token, KEY).unwrap(), b"user=7");
+    }
+
+    #[test]
+    fn test_plain_token_over_a_kind_label_does_not_reveal_its_key() {
+        let token = encode_and_sign(b"ancryptor/expiring", KEY);
+        let (_, signature) = token.split_once('.').unwrap();
+        let leaked_key = base64UrlSafeNoPadEngine.decode(signature).unwrap();
+
+        let forged = sign_with_timestamp(TokenKind::Signed, b"reset=7", u64::MAX, &leaked_key);
+
+        assert_eq!(decode_expiring(&forged, KEY), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_token_is_url_safe() {
+        let token = encode_and_sign(&[0xFB, 0xFF, 0xFE], KEY);
+
+        assert!(!token.contains(['+', '/', '=']));
+    }
+
+    #[test]
+    fn test_tampered_payload_is_rejected() {
+        let token = encode_and_sign(b"user=7", KEY);
+        let (_, signature) = token.split_once('.').unwrap();
+        let tampered = format!("{}.{}", base64UrlSafeNoPadEngine.encode(b"user=8"), signature);
+
+        assert_eq!(verify_and_decode(&tampered, KEY), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_wrong_key_is_rejected() {
+        let token = encode_and_sign(b"user=7", KEY);
+
+        assert_eq!(verify_and_decode(&token, b"other-key"), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_token_without_separator_is_rejected() {
+        let token = encode_and_sign(b"user=7", KEY).replace('.', "");
+
+        assert_eq!(verify_and_decode(&token, KEY), Err(AncryptorError::MalformedToken));
+    }
+
+    #[test]
+    fn test_token_with_invalid_base64_is_rejected() {
+        assert_eq!(verify_and_decode("not base64!.c2ln", KEY), Err(AncryptorError::MalformedToken));
+        assert_eq!(verify_and_decode("AXVzZXI9Nw.not base64!", KEY), Err(AncryptorError::MalformedToken));
+    }
+
+    #[test]
+    fn test_expiring_token_round_trip() {
+        let token = encode_expiring(b"reset=7", Duration::from_secs(60), KEY);
+
+        assert_eq!(decode_expiring(&token, KEY).unwrap(), b"reset=7");
+    }
+
+    #[test]
+    fn test_already_expired_token_is_rejected() {
+        let token = encode_expiring_at(b"reset=7", unix_time_now() - 1, KEY);
+
+        assert_eq!(decode_expiring(&token, KEY), Err(AncryptorError::Expired));
+    }
+
+    #[test]
+    fn test_expiry_is_checked_against_now() {
+        let token = encode_expiring_at(b"reset=7", 1_000, KEY);
+
+        assert_eq!(decode_expiring_at(&token, KEY, 999).unwrap(), b"reset=7");
+        assert_eq!(decode_expiring_at(&token, KEY, 1_000), Err(AncryptorError::Expired));
+    }
+
+    #[test]
+    fn test_forged_expiry_is_rejected() {
+        let token = encode_expiring_at(b"reset=7", 1_000, KEY);
+        let (_, signature) = token.split_once('.').unwrap();
+
+        let mut forged_payload = u64::MAX.to_be_bytes().to_vec();
+        forged_payload.extend_from_slice(b"reset=7");
+        let forged = format!("{}.{}", base64UrlSafeNoPadEngine.encode(forged_payload), signature);
+
+        assert_eq!(decode_expiring_at(&forged, KEY, 2_000), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_expiring_token_too_short_for_expiry() {
+        let token = sign_as(TokenKind::Expiring, b"short", KEY);
+
+        assert_eq!(decode_expiring(&token, KEY), Err(AncryptorError::MalformedToken));
+    }
+
+    #[test]
+    fn test_plain_signed_token_is_not_an_expiring_one() {
+        // Under the same key its first 8 bytes would read as an expiry
+        // about 7e18 seconds away.
+        let token = encode_and_sign(b"admin=1;role=ops", KEY);
+
+        assert_eq!(decode_expiring(&token, KEY), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_timestamped_round_trip_reports_creation_time() {
+        let before = unix_time_now();
+        let token = encode_timestamped(b"login user=7", KEY);
+        let (created_at, payload) = decode_timestamped(&token, KEY).unwrap();
+
+        assert!((before..=unix_time_now()).contains(&created_at));
+        assert_eq!(payload, b"login user=7");
+    }
+
+    #[test]
+    fn test_timestamped_old_records_are_not_rejected() {
+        let token = sign_with_timestamp(TokenKind::Timestamped, b"login user=7", 1, KEY);
+
+        assert_eq!(decode_timestamped(&token, KEY).unwrap(), (1, b"login user=7".to_vec()));
+    }
+
+    #[test]
+    fn test_timestamped_tampered_timestamp_fails() {
+        let token = sign_with_timestamp(TokenKind::Timestamped, b"login user=7", 1_700_000_000, KEY);
+        let (payload, signature) = token.split_once('.').unwrap();
+
+        let mut payload = base64UrlSafeNoPadEngine.decode(payload).unwrap();
+        payload[..TIMESTAMP_LEN].copy_from_slice(&1_600_000_000u64.to_be_bytes());
+        let tampered = format!("{}.{}", base64UrlSafeNoPadEngine.encode(payload), signature);
+
+        assert_eq!(decode_timestamped(&tampered, KEY), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_timestamped_rejects_other_token_kinds() {
+        let expiring = encode_expiring(b"login user=7", Duration::from_secs(60), KEY);
+        let signed = encode_and_sign(b"login user=7", KEY);
+
+        assert_eq!(decode_timestamped(&expiring, KEY), Err(AncryptorError::InvalidSignature));
+        assert_eq!(decode_timestamped(&signed, KEY), Err(AncryptorError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_timestamped_token_is_not_an_expiring_one() {
+        let token = encode_timestamped(b"login user=7", KEY);
+
+        assert_eq!(decode_expiring(&token, KEY), Err(AncryptorError::InvalidSignature));
+        assert_eq!(verify_and_decode(&token, KEY), Err(AncryptorError::InvalidSignature));
+    }
+}
@@ -0,0 +1,94 @@
+//!
+//! Allocation counts of the encoding hot path, so a refactor that adds
+//! a heap allocation per call fails here instead of going unnoticed.
+//!
+//! This file is its own test binary, which lets it install a counting
+//! `#[global_allocator]` without affecting the other tests. The
+//! allocator forwards to the system allocator and counts every
+//! `alloc`/`realloc` made by the current thread; counts are per thread
+//! because the test harness runs tests in parallel.
+//!
+//! ## Examples
+//! ```
+//! $ cd ancryptor/
+//! $ cargo test --test allocation_tests
+//! ```
+//!
+
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+
+use ancryptor::{encoded_len, EncodingKind};
+#[cfg(feature = "std")]
+use ancryptor::Codec;
+
+static INPUT: &str = "hello_world_from_rust, encoded on the hot path";
+
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        ALLOCATIONS.with(|allocations| allocations.set(allocations.get() + 1));
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+#[global_allocator]
+static ALLOCATOR: CountingAllocator = CountingAllocator;
+
+///
+/// Runs `f` and returns how many allocations it made on this thread.
+///
+fn allocations_during<T>(f: impl FnOnce() -> T) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    let result = f();
+    let after = ALLOCATIONS.with(Cell::get);
+
+    drop(result);
+    after - before
+}
+
+#[test]
+fn test_encode_into_presized_buffer_does_not_allocate() {
+    let mut output = String::with_capacity(encoded_len(INPUT.len(), EncodingKind::Base64));
+
+    assert_eq!(allocations_during(|| ancryptor::encode_into(INPUT, &mut output)), 0);
+    assert_eq!(output, ancryptor::encode(INPUT));
+}
+
+#[test]
+fn test_encode_into_reused_buffer_does_not_allocate() {
+    let mut output = String::with_capacity(encoded_len(INPUT.len(), EncodingKind::Base64));
+
+    for _ in 0..100 {
+        output.clear();
+        assert_eq!(allocations_during(|| ancryptor::encode_into(INPUT, &mut output)), 0);
+    }
+}
+
+#[test]
+fn test_encode_allocates_exactly_once() {
+    assert_eq!(allocations_during(|| ancryptor::encode(INPUT)), 1);
+    assert_eq!(allocations_during(|| ancryptor::encode_bytes(INPUT.as_bytes())), 1);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_codec_encode_allocates_exactly_once() {
+    for kind in [EncodingKind::Base64, EncodingKind::Base64UrlSafe, EncodingKind::Hex] {
+        assert_eq!(allocations_during(|| kind.encode(INPUT.as_bytes())), 1, "{:?}", kind);
+    }
+}
@@ -0,0 +1,112 @@
+#![cfg(feature = "std")]
+
+use std::io::Write;
+use std::process::{Command, Output, Stdio};
+
+static ANCRYPT: &str = env!("CARGO_BIN_EXE_ancrypt");
+
+fn run_ancrypt(args: &[&str], envs: &[(&str, &str)], stdin: &[u8]) -> Output {
+    let mut child = Command::new(ANCRYPT)
+        .args(args)
+        .env_remove("ANCRYPTOR_KEY")
+        .envs(envs.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .expect("Failed to start ancrypt");
+
+    child.stdin.take().unwrap().write_all(stdin).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn test_cli_seal_and_open_with_key_from_env() {
+    let key = ancryptor::encode_bytes(&[9; 32]);
+    let env = [("ANCRYPTOR_KEY", key.as_str())];
+
+    let sealed = run_ancrypt(&["seal"], &env, b"hello_world_from_rust");
+    assert!(sealed.status.success());
+
+    let opened = run_ancrypt(&["open"], &env, &sealed.stdout);
+    assert!(opened.status.success());
+    assert_eq!(opened.stdout, b"hello_world_from_rust");
+}
+
+#[test]
+fn test_cli_key_file() {
+    let mut key_file = tempfile::NamedTempFile::new().unwrap();
+    writeln!(key_file, "{}", ancryptor::encode_bytes(&[9; 32])).unwrap();
+    let key_path = key_file.path().to_str().unwrap();
+
+    let sealed = run_ancrypt(&["seal", "--key-file", key_path], &[], b"from a file");
+    let opened = run_ancrypt(&["open", "--key-file", key_path], &[], &sealed.stdout);
+
+    assert_eq!(opened.stdout, b"from a file");
+}
+
+#[test]
+fn test_cli_warns_about_inline_key() {
+    let key = ancryptor::encode_bytes(&[9; 32]);
+
+    let sealed = run_ancrypt(&["seal", "--key", &key], &[], b"data");
+
+    assert!(sealed.status.success());
+    assert!(String::from_utf8_lossy(&sealed.stderr).contains("warning"));
+}
+
+#[test]
+fn test_cli_without_key_fails() {
+    let sealed = run_ancrypt(&["seal"], &[], b"data");
+
+    assert!(!sealed.status.success());
+    assert!(sealed.stdout.is_empty());
+}
+
+#[test]
+fn test_cli_decode_hexdump_needs_no_key() {
+    let encoded = ancryptor::encode_bytes(b"hello_world_from_rust");
+
+    let dumped = run_ancrypt(&["decode", "--hexdump"], &[], encoded.as_bytes());
+
+    assert!(dumped.status.success());
+    assert_eq!(dumped.stdout, ancryptor::hexdump(b"hello_world_from_rust").as_bytes());
+}
+
+#[test]
+fn test_cli_encode() {
+    let encoded = run_ancrypt(&["encode"], &[], b"hello_world_from_rust");
+
+    assert!(encoded.status.success());
+    assert_eq!(encoded.stdout, b"aGVsbG9fd29ybGRfZnJvbV9ydXN0\n");
+}
+
+#[test]
+fn test_cli_encode_and_decode_url_safe() {
+    let encoded = run_ancrypt(&["encode", "--url-safe"], &[], b"??>");
+    assert_eq!(encoded.stdout, b"Pz8-\n");
+
+    let decoded = run_ancrypt(&["decode", "--url-safe"], &[], &encoded.stdout);
+    assert!(decoded.status.success());
+    assert_eq!(decoded.stdout, b"??>");
+}
+
+#[test]
+fn test_cli_encode_and_decode_fail_on_empty_input() {
+    for command in ["encode", "decode"] {
+        let output = run_ancrypt(&[command], &[], b"");
+
+        assert!(!output.status.success());
+        assert!(output.stdout.is_empty());
+        assert!(String::from_utf8_lossy(&output.stderr).contains("no input"));
+    }
+}
+
+#[test]
+fn test_cli_decode_invalid_base64_fails() {
+    let decoded = run_ancrypt(&["decode"], &[], b"not base64!");
+
+    assert!(!decoded.status.success());
+    assert!(decoded.stdout.is_empty());
+    assert!(String::from_utf8_lossy(&decoded.stderr).contains("invalid base64"));
+}
@@ -1,3 +1,7 @@
+// The first tests predate clippy being part of the checks and are kept
+// as they were written.
+#![allow(clippy::needless_borrow, clippy::single_component_path_imports)]
+
 use ancryptor;
 
 #[test]
@@ -35,5 +39,240 @@ fn test_decrypt_invalid_base64_string() {
 
     let decrypted_result: String = ancryptor::decode(&invalid_base64_str);
 
-    assert_eq!("", decrypted_result)
-}
\ No newline at end of file
+    assert_eq!("", decrypted_result);
+    assert!(matches!(ancryptor::try_decode(invalid_base64_str), Err(ancryptor::DecodeError::InvalidBase64(_))));
+}
+
+#[test]
+fn test_decrypt_string_with_bom() {
+    let bom_prefixed_b64 = "\u{FEFF}aGVsbG9fd29ybGRfZnJvbV9ydXN0";
+
+    assert_eq!("hello_world_from_rust", ancryptor::decode(bom_prefixed_b64));
+    assert_eq!(b"hello_world_from_rust".to_vec(), ancryptor::decode_bytes(bom_prefixed_b64).unwrap());
+}
+
+#[test]
+fn test_decode_lossy_replaces_invalid_utf8() {
+    let encoded = ancryptor::encode_bytes(&[0x68, 0x69, 0xFF]);
+
+    assert_eq!("hi\u{FFFD}", ancryptor::decode_lossy(&encoded));
+    assert_eq!(Ok(vec![0x68, 0x69, 0xFF]), ancryptor::decode_bytes(&encoded));
+}
+
+#[test]
+fn test_decode_lossy_keeps_valid_prefix() {
+    let encoded = ancryptor::encode_bytes(b"valid prefix\xFF\xFE");
+
+    assert_eq!("", ancryptor::decode(&encoded));
+    assert_eq!("valid prefix\u{FFFD}\u{FFFD}", ancryptor::decode_lossy(&encoded));
+}
+
+#[test]
+fn test_decode_lossy_invalid_base64_string() {
+    assert_eq!("", ancryptor::decode_lossy("dfoiuerw892"));
+}
+
+#[test]
+fn test_decode_tolerant_whitespace_only_is_empty() {
+    for whitespace_only in ["   ", "\t\t", "\n\r\n", " \t\n "] {
+        assert_eq!(Ok(vec![]), ancryptor::decode_tolerant(whitespace_only), "{:?}", whitespace_only);
+    }
+}
+
+#[test]
+fn test_decode_tolerant_ignores_line_breaks() {
+    let str_encoded_b64 = "aGVsbG9fd29ybGRfZnJvbV9ydXN0";
+    let wrapped: String = str_encoded_b64.as_bytes()
+        .chunks(10)
+        .map(|line| std::str::from_utf8(line).unwrap())
+        .collect::<Vec<_>>()
+        .join("\n");
+
+    assert_eq!(wrapped.lines().count(), 3);
+    assert_eq!("", ancryptor::decode(&wrapped));
+    assert_eq!(Ok(b"hello_world_from_rust".to_vec()), ancryptor::decode_tolerant(&wrapped));
+}
+
+#[test]
+fn test_decode_tolerant_ignores_surrounding_whitespace() {
+    let padded_b64 = "  aGVsbG9fd29ybGRfZnJvbV9ydXN0\n";
+
+    assert_eq!(Ok(b"hello_world_from_rust".to_vec()), ancryptor::decode_tolerant(padded_b64));
+}
+
+#[test]
+fn test_try_decode_reports_the_failure() {
+    assert_eq!(Ok("hello_world_from_rust".to_owned()), ancryptor::try_decode("aGVsbG9fd29ybGRfZnJvbV9ydXN0"));
+    assert!(matches!(ancryptor::try_decode("dfoiuerw892"), Err(ancryptor::DecodeError::InvalidBase64(_))));
+    assert!(matches!(ancryptor::try_decode("aGn/"), Err(ancryptor::DecodeError::InvalidUtf8(_))));
+}
+
+#[test]
+fn test_try_encode_matches_encode() {
+    assert_eq!(Ok(ancryptor::encode("hello_world_from_rust")), ancryptor::try_encode("hello_world_from_rust"));
+    assert_eq!(Ok("".to_owned()), ancryptor::try_encode(""));
+}
+
+#[test]
+fn test_url_safe_replaces_plus_and_slash() {
+    assert_eq!("Pz8+Pz8/", ancryptor::encode("??>???"));
+    assert_eq!("Pz8-Pz8_", ancryptor::encode_url_safe("??>???"));
+    assert_eq!("??>???", ancryptor::decode_url_safe("Pz8-Pz8_"));
+    assert_eq!("hello_world_from_rust", ancryptor::decode_url_safe(&ancryptor::encode_url_safe("hello_world_from_rust")));
+}
+
+#[test]
+fn test_url_safe_and_standard_reject_each_other() {
+    assert_eq!("", ancryptor::decode("Pz8-Pz8_"));
+    assert_eq!("", ancryptor::decode_url_safe("Pz8+Pz8/"));
+}
+
+#[test]
+fn test_byte_apis_round_trip_non_utf8_bytes() {
+    for bytes in [&[0xFF, 0xFE, 0x00][..], &[0x00], &[0x80, 0x81, 0xC0], &[0x89, b'P', b'N', b'G', 0x0D, 0x0A, 0x1A, 0x0A]] {
+        let encoded = ancryptor::encode_bytes(bytes);
+
+        assert_eq!(Ok(bytes.to_vec()), ancryptor::decode_bytes(&encoded));
+    }
+
+    assert_eq!("//4A", ancryptor::encode_bytes(&[0xFF, 0xFE, 0x00]));
+    assert_eq!("", ancryptor::decode("//4A"));
+}
+
+#[test]
+fn test_version_is_semver() {
+    let version = ancryptor::version();
+    let (release, _pre_release) = version.split_once('-').unwrap_or((version, ""));
+    let parts: Vec<&str> = release.split('.').collect();
+
+    assert!(!version.is_empty());
+    assert_eq!(3, parts.len(), "{}", version);
+    assert!(parts.iter().all(|part| !part.is_empty() && part.bytes().all(|byte| byte.is_ascii_digit())), "{}", version);
+}
+
+#[test]
+fn test_decode_owned_matches_decode() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    sender.send(ancryptor::encode("hello_world_from_rust")).unwrap();
+
+    let received: String = receiver.recv().unwrap();
+    let expected = ancryptor::decode(&received);
+
+    assert_eq!(Ok(expected.into_bytes()), ancryptor::decode_owned(received));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_decode_error_converts_to_invalid_data_io_error() {
+    let error = ancryptor::decode_grouped("not base64!", '-').unwrap_err();
+    let io_error: std::io::Error = error.into();
+
+    assert_eq!(std::io::ErrorKind::InvalidData, io_error.kind());
+    assert!(io_error.to_string().contains("invalid base64"));
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_io_error_round_trips_through_ancryptor_error() {
+    let io_error = std::io::Error::new(std::io::ErrorKind::NotFound, "no such file");
+    let error: ancryptor::AncryptorError = io_error.into();
+
+    assert_eq!(ancryptor::AncryptorError::Io(std::io::ErrorKind::NotFound, "no such file".to_owned()), error);
+    assert_eq!(std::io::ErrorKind::NotFound, std::io::Error::from(error).kind());
+}
+
+#[test]
+fn test_constant_time_eq_equal_inputs() {
+    assert!(ancryptor::constant_time_eq(b"hello_world_from_rust", b"hello_world_from_rust"));
+    assert!(ancryptor::constant_time_eq(b"", b""));
+}
+
+#[test]
+fn test_constant_time_eq_unequal_inputs_of_same_length() {
+    assert!(!ancryptor::constant_time_eq(b"hello_world_from_rust", b"hello_world_from_rusT"));
+    assert!(!ancryptor::constant_time_eq(b"hello_world_from_rust", b"Hello_world_from_rust"));
+}
+
+#[test]
+fn test_constant_time_eq_inputs_of_different_length() {
+    assert!(!ancryptor::constant_time_eq(b"hello", b"hello_world_from_rust"));
+    assert!(!ancryptor::constant_time_eq(b"hello", b""));
+}
+
+#[test]
+fn test_ancryptor_error_display_is_stable() {
+    use ancryptor::{AncryptorError, CryptoError, DecodeError};
+
+    let invalid_base64 = AncryptorError::Decode(DecodeError::InvalidBase64(base64::DecodeError::InvalidLength));
+
+    assert_eq!(invalid_base64.to_string(), "invalid base64: Encoded text cannot have a 6-bit remainder.");
+    assert_eq!(AncryptorError::Crypto(CryptoError::AuthenticationFailed).to_string(), "ciphertext failed authentication");
+    assert_eq!(AncryptorError::MissingParam("data".to_owned()).to_string(), "missing query parameter 'data'");
+    assert_eq!(AncryptorError::ChecksumMismatch.to_string(), "checksum mismatch");
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn test_question_mark_converts_into_ancryptor_error() {
+    use base64::Engine as _;
+
+    fn decode_text(from: &str) -> Result<String, ancryptor::AncryptorError> {
+        let bytes = base64::engine::general_purpose::STANDARD.decode(from)?;
+        Ok(String::from_utf8(bytes)?)
+    }
+
+    fn decrypt_text(from: &str) -> Result<String, ancryptor::AncryptorError> {
+        Ok(ancryptor::decrypt(from, &[7; 32])?)
+    }
+
+    assert_eq!(decode_text("aGk="), Ok("hi".to_owned()));
+    assert!(matches!(decode_text("dfoiuerw892"), Err(ancryptor::AncryptorError::Decode(ancryptor::DecodeError::InvalidBase64(_)))));
+    assert!(matches!(decode_text("aGn/"), Err(ancryptor::AncryptorError::Decode(ancryptor::DecodeError::InvalidUtf8(_)))));
+    assert!(matches!(decrypt_text("aGk="), Err(ancryptor::AncryptorError::Crypto(_))));
+}
+
+#[test]
+fn test_is_valid_base64_empty_string() {
+    assert!(ancryptor::is_valid_base64(""));
+    assert!(ancryptor::is_valid_base64_url_safe(""));
+}
+
+#[test]
+fn test_is_valid_base64_valid_inputs() {
+    assert!(ancryptor::is_valid_base64("aGVsbG9fd29ybGRfZnJvbV9ydXN0"));
+    assert!(ancryptor::is_valid_base64("aGk="));
+    assert!(ancryptor::is_valid_base64("\u{FEFF}aGVsbG9fd29ybGRfZnJvbV9ydXN0"));
+    assert!(ancryptor::is_valid_base64(&ancryptor::encode_bytes(&[0xAB; 5000])));
+    assert!(ancryptor::is_valid_base64_url_safe("Pz8-Pz8_"));
+}
+
+#[test]
+fn test_is_valid_base64_invalid_inputs() {
+    for invalid in ["dfoiuerw892", "aGk", "aGk=aGk=", "aGk=\n", "aGj=", "Pz8-Pz8_"] {
+        assert!(!ancryptor::is_valid_base64(invalid), "{:?}", invalid);
+        assert!(ancryptor::decode_bytes(invalid).is_err(), "{:?}", invalid);
+    }
+
+    assert!(!ancryptor::is_valid_base64_url_safe("Pz8+Pz8/"));
+    assert!(!ancryptor::is_valid_base64_url_safe("dfoiuerw892"));
+}
+
+#[test]
+fn test_is_valid_base64_rejects_padding_before_the_end_of_long_input() {
+    // 256 padded groups fill exactly one validation chunk of 1024 characters.
+    let padded_early = format!("{}aGVsbG8h", "aGk=".repeat(256));
+
+    assert!(!ancryptor::is_valid_base64(&padded_early));
+    assert!(ancryptor::decode_bytes(&padded_early).is_err());
+    assert!(ancryptor::is_valid_base64(&"aGVsbG8h".repeat(300)));
+}
+
+#[test]
+fn test_is_valid_base64_ignores_bom_in_both_alphabets() {
+    let with_bom = "\u{FEFF}Pz8_YWJj";
+
+    assert!(ancryptor::is_valid_base64_url_safe(with_bom));
+    assert_eq!(ancryptor::decode_url_safe(with_bom), "???abc");
+    assert!(ancryptor::is_valid_base64("\u{FEFF}Pz8/YWJj"));
+    assert!(!ancryptor::is_valid_base64_url_safe("\u{FEFF}\u{FEFF}Pz8_YWJj"));
+}
@@ -0,0 +1,66 @@
+//!
+//! Builds the crate without its default `std` feature, alone and with
+//! each feature that is meant to work without `std`, so the `no_std`
+//! core cannot quietly start depending on `std` again.
+//!
+//! The builds target `thumbv7em-none-eabihf`, which has no `std` at
+//! all, when it is installed; otherwise they fall back to the host,
+//! which still catches `std` used by the crate itself but not by its
+//! dependencies.
+//!
+//! ## Examples
+//! ```
+//! $ rustup target add thumbv7em-none-eabihf
+//! $ cd ancryptor/
+//! $ cargo test --test no_std_build_tests
+//! ```
+//!
+
+use std::path::Path;
+use std::process::Command;
+
+static NO_STD_TARGET: &str = "thumbv7em-none-eabihf";
+
+fn no_std_target_installed() -> bool {
+    let sysroot = Command::new(std::env::var("RUSTC").unwrap_or_else(|_| "rustc".to_owned()))
+        .args(["--print", "sysroot"])
+        .output()
+        .expect("Failed to run rustc");
+    let sysroot = String::from_utf8(sysroot.stdout).unwrap();
+
+    Path::new(sysroot.trim()).join("lib/rustlib").join(NO_STD_TARGET).is_dir()
+}
+
+fn build_without_std(features: &str) {
+    let mut cargo = Command::new(env!("CARGO"));
+    cargo.current_dir(env!("CARGO_MANIFEST_DIR"))
+        .args(["build", "--lib", "--no-default-features", "--features", features])
+        // A separate target directory, as the outer `cargo test` holds the lock on its own.
+        .arg("--target-dir")
+        .arg(Path::new(env!("CARGO_TARGET_TMPDIR")).join("no_std_build"));
+
+    if no_std_target_installed() {
+        cargo.args(["--target", NO_STD_TARGET]);
+    }
+
+    let output = cargo.output().expect("Failed to run cargo");
+
+    assert!(
+        output.status.success(),
+        "no_std build with features [{}] failed:\n{}",
+        features,
+        String::from_utf8_lossy(&output.stderr),
+    );
+}
+
+#[test]
+fn test_builds_without_std() {
+    build_without_std("");
+}
+
+#[test]
+fn test_builds_without_std_with_hmac_and_base58() {
+    for features in ["hmac", "base58", "hmac,base58"] {
+        build_without_std(features);
+    }
+}
@@ -0,0 +1,107 @@
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, Index};
+
+///
+/// `#[derive(Encodable)]` implements `ancryptor::Encodable` for a struct,
+/// storing each field as one frame of an `ancryptor::encode_framed` blob,
+/// in declaration order.
+///
+/// Every field type must implement `ancryptor::FrameField`, which covers
+/// the primitive numbers, `bool`, `String` and `Vec<u8>`.
+///
+/// ## Example
+/// ```
+/// use ancryptor::Encodable;
+/// use ancryptor_derive::Encodable;
+///
+/// #[derive(Encodable, Debug, PartialEq)]
+/// struct Token {
+///     id: u32,
+///     owner: String,
+/// }
+///
+/// let token = Token { id: 7, owner: "abhay".to_owned() };
+/// let encoded = token.encode();
+///
+/// assert_eq!(Token::decode(&encoded).unwrap(), token);
+/// ```
+///
+/// For more information, refer to the official doc:
+///  - https://doc.rust-lang.org/reference/procedural-macros.html#derive-macros
+///
+#[proc_macro_derive(Encodable)]
+pub fn derive_encodable(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+
+    expand_encodable(&input)
+        .unwrap_or_else(|error| error.to_compile_error())
+        .into()
+}
+
+fn expand_encodable(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let fields = match &input.data {
+        Data::Struct(data) => &data.fields,
+        _ => return Err(syn::Error::new_spanned(
+            name,
+            "`Encodable` can only be derived for structs",
+        )),
+    };
+
+    let field_count = fields.len();
+
+    // `self.a` / `self.0` accessors used when encoding.
+    let accessors: Vec<TokenStream2> = match fields {
+        Fields::Named(named) => named.named.iter().map(|field| {
+            let ident = &field.ident;
+            quote! { self.#ident }
+        }).collect(),
+        Fields::Unnamed(unnamed) => (0..unnamed.unnamed.len()).map(|index| {
+            let index = Index::from(index);
+            quote! { self.#index }
+        }).collect(),
+        Fields::Unit => Vec::new(),
+    };
+
+    // One `FrameField::from_frame(&parts[i])?` per field, in order.
+    let decoded: Vec<TokenStream2> = fields.iter().enumerate().map(|(index, field)| {
+        let ty = &field.ty;
+        quote! { <#ty as ::ancryptor::FrameField>::from_frame(&parts[#index])? }
+    }).collect();
+
+    let constructor = match fields {
+        Fields::Named(named) => {
+            let idents = named.named.iter().map(|field| &field.ident);
+            quote! { Self { #(#idents: #decoded),* } }
+        }
+        Fields::Unnamed(_) => quote! { Self ( #(#decoded),* ) },
+        Fields::Unit => quote! { Self },
+    };
+
+    Ok(quote! {
+        impl #impl_generics ::ancryptor::Encodable for #name #type_generics #where_clause {
+            fn encode(&self) -> ::std::string::String {
+                let frames: [::std::vec::Vec<u8>; #field_count] = [
+                    #(::ancryptor::FrameField::to_frame(&#accessors)),*
+                ];
+                let parts: ::std::vec::Vec<&[u8]> = frames.iter().map(|frame| frame.as_slice()).collect();
+
+                ::ancryptor::encode_framed(&parts)
+            }
+
+            fn decode(from: &str) -> ::std::result::Result<Self, ::ancryptor::AncryptorError> {
+                let parts = ::ancryptor::decode_framed(from)?;
+
+                if parts.len() != #field_count {
+                    return ::std::result::Result::Err(::ancryptor::AncryptorError::MalformedFrame);
+                }
+
+                ::std::result::Result::Ok(#constructor)
+            }
+        }
+    })
+}
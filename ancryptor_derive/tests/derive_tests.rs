@@ -0,0 +1,43 @@
+use ancryptor::{AncryptorError, Encodable};
+use ancryptor_derive::Encodable;
+
+#[derive(Encodable, Debug, PartialEq)]
+struct Record {
+    id: u64,
+    payload: Vec<u8>,
+}
+
+#[derive(Encodable, Debug, PartialEq)]
+struct Pair(String, bool);
+
+#[test]
+fn test_derived_struct_round_trips() {
+    let record = Record { id: 42, payload: vec![0xFF, 0x00, 0x7F] };
+
+    let encoded = record.encode();
+
+    assert_eq!(Record::decode(&encoded), Ok(record));
+}
+
+#[test]
+fn test_derived_struct_uses_framed_encoding() {
+    let record = Record { id: 1, payload: b"hi".to_vec() };
+
+    let parts = ancryptor::decode_framed(&record.encode()).unwrap();
+
+    assert_eq!(parts, vec![1u64.to_be_bytes().to_vec(), b"hi".to_vec()]);
+}
+
+#[test]
+fn test_derived_tuple_struct_round_trips() {
+    let pair = Pair("hello_world_from_rust".to_owned(), true);
+
+    assert_eq!(Pair::decode(&pair.encode()), Ok(pair));
+}
+
+#[test]
+fn test_derived_decode_rejects_wrong_field_count() {
+    let encoded = ancryptor::encode_framed(&[b"only one frame"]);
+
+    assert_eq!(Record::decode(&encoded), Err(AncryptorError::MalformedFrame));
+}
@@ -0,0 +1,21 @@
+//!
+//! Generates the C header `include/ancryptor.h` from the `extern "C"`
+//! functions in `src/lib.rs`, using the settings in `cbindgen.toml`.
+//!
+
+use std::env;
+use std::path::PathBuf;
+
+static HEADER_FILE_PATH: &str = "include/ancryptor.h";
+
+fn main() {
+    let crate_dir = PathBuf::from(env::var("CARGO_MANIFEST_DIR").expect("Cannot read CARGO_MANIFEST_DIR"));
+    let config = cbindgen::Config::from_file(crate_dir.join("cbindgen.toml")).expect("Cannot read cbindgen.toml");
+
+    cbindgen::generate_with_config(&crate_dir, config)
+        .expect("Cannot generate the C header")
+        .write_to_file(crate_dir.join(HEADER_FILE_PATH));
+
+    println!("cargo:rerun-if-changed=src/lib.rs");
+    println!("cargo:rerun-if-changed=cbindgen.toml");
+}
@@ -0,0 +1,55 @@
+#ifndef ANCRYPTOR_H
+#define ANCRYPTOR_H
+
+/* Generated by cbindgen from src/lib.rs, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+//
+// Encodes `input` as standard, padded base64.
+//
+// Returns null if `input` is null or not valid UTF-8. Release the
+// result with `ancryptor_free_string`.
+//
+// # Safety
+//
+// `input` must be null or point to a NUL-terminated string.
+//
+char *ancryptor_encode(const char *input);
+
+//
+// Decodes standard, padded base64 into a string.
+//
+// Returns null if `input` is null or not valid base64, or if the decoded
+// bytes are not valid UTF-8 or contain a NUL byte. Release the result
+// with `ancryptor_free_string`.
+//
+// # Safety
+//
+// `input` must be null or point to a NUL-terminated string.
+//
+char *ancryptor_decode(const char *input);
+
+//
+// Releases a string returned by `ancryptor_encode` or
+// `ancryptor_decode`. Null is ignored.
+//
+// # Safety
+//
+// `string` must be null or a pointer returned by this library that was
+// not released yet.
+//
+void ancryptor_free_string(char *string);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* ANCRYPTOR_H */
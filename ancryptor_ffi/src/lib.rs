@@ -0,0 +1,142 @@
+//!
+//! C ABI for the `ancryptor` crate, for C/C++ and Swift callers; the
+//! header is `include/ancryptor.h`.
+//!
+//! Strings go in and out as NUL-terminated UTF-8. Every returned string
+//! is owned by the caller and must be released with
+//! `ancryptor_free_string`, never with `free`. On any error, including a
+//! null input, the functions return null instead of unwinding into C.
+//!
+//! ## Examples
+//! ```c
+//! #include "ancryptor.h"
+//!
+//! char *encoded = ancryptor_encode("hello");
+//! char *decoded = ancryptor_decode(encoded);   // "hello"
+//! ancryptor_free_string(encoded);
+//! ancryptor_free_string(decoded);
+//! ```
+//!
+
+use std::ffi::{c_char, CStr, CString};
+use std::panic;
+use std::ptr;
+
+///
+/// Applies `convert` to the string behind `input` and hands the result
+/// over to C, or returns null if `input` is null or not UTF-8, if
+/// `convert` fails or panics, or if the result contains a NUL byte.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string.
+///
+unsafe fn convert_c_string(input: *const c_char, convert: fn(&str) -> Option<String>) -> *mut c_char {
+    if input.is_null() {
+        return ptr::null_mut();
+    }
+
+    let Ok(input) = CStr::from_ptr(input).to_str() else {
+        return ptr::null_mut();
+    };
+
+    match panic::catch_unwind(|| convert(input)) {
+        Ok(Some(output)) => CString::new(output).map_or(ptr::null_mut(), CString::into_raw),
+        _ => ptr::null_mut(),
+    }
+}
+
+///
+/// Encodes `input` as standard, padded base64.
+///
+/// Returns null if `input` is null or not valid UTF-8. Release the
+/// result with `ancryptor_free_string`.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string.
+///
+#[no_mangle]
+pub unsafe extern "C" fn ancryptor_encode(input: *const c_char) -> *mut c_char {
+    convert_c_string(input, |input| Some(ancryptor::encode(input)))
+}
+
+///
+/// Decodes standard, padded base64 into a string.
+///
+/// Returns null if `input` is null or not valid base64, or if the decoded
+/// bytes are not valid UTF-8 or contain a NUL byte. Release the result
+/// with `ancryptor_free_string`.
+///
+/// # Safety
+///
+/// `input` must be null or point to a NUL-terminated string.
+///
+#[no_mangle]
+pub unsafe extern "C" fn ancryptor_decode(input: *const c_char) -> *mut c_char {
+    convert_c_string(input, |input| ancryptor::try_decode(input).ok())
+}
+
+///
+/// Releases a string returned by `ancryptor_encode` or
+/// `ancryptor_decode`. Null is ignored.
+///
+/// # Safety
+///
+/// `string` must be null or a pointer returned by this library that was
+/// not released yet.
+///
+#[no_mangle]
+pub unsafe extern "C" fn ancryptor_free_string(string: *mut c_char) {
+    if !string.is_null() {
+        drop(CString::from_raw(string));
+    }
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_then_decode_round_trips() {
+        unsafe {
+            let encoded = ancryptor_encode(c"hello_world_from_rust".as_ptr());
+            assert_eq!(CStr::from_ptr(encoded), c"aGVsbG9fd29ybGRfZnJvbV9ydXN0");
+
+            let decoded = ancryptor_decode(encoded);
+            assert_eq!(CStr::from_ptr(decoded), c"hello_world_from_rust");
+
+            ancryptor_free_string(encoded);
+            ancryptor_free_string(decoded);
+        }
+    }
+
+    #[test]
+    fn test_null_input_returns_null() {
+        unsafe {
+            assert!(ancryptor_encode(ptr::null()).is_null());
+            assert!(ancryptor_decode(ptr::null()).is_null());
+            ancryptor_free_string(ptr::null_mut());
+        }
+    }
+
+    #[test]
+    fn test_decode_failures_return_null() {
+        unsafe {
+            // invalid base64, invalid UTF-8, and a decoded NUL byte
+            for invalid in [c"dfoiuerw892", c"aGn/", c"aABp"] {
+                assert!(ancryptor_decode(invalid.as_ptr()).is_null(), "{:?}", invalid);
+            }
+        }
+    }
+
+    #[test]
+    fn test_non_utf8_input_returns_null() {
+        unsafe {
+            assert!(ancryptor_encode(c"\xFF\xFE".as_ptr()).is_null());
+        }
+    }
+}
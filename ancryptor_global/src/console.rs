@@ -1,15 +1,27 @@
+use std::env;
+use std::ffi::OsString;
 use std::process::Command;
 use std::io::{
     self, 
+    IsTerminal,
     Write,
 };
 
+// ANSI escape codes, see https://en.wikipedia.org/wiki/ANSI_escape_code#Colors
+static GREEN: &str = "\x1b[32m";
+static YELLOW: &str = "\x1b[33m";
+static RED: &str = "\x1b[31m";
+static RESET: &str = "\x1b[0m";
+
+// Disables colors when set to anything but an empty string, see https://no-color.org
+static NO_COLOR_ENV_VAR: &str = "NO_COLOR";
+
 
 /// Runs a command line command.
 /// 
 /// ## Example:
 /// ```
-/// use cryptor_global::console::run_command;
+/// use ancryptor_global::console::run_command;
 /// 
 /// let mut command_args = Vec::new();
 /// command_args.push("ls");
@@ -34,7 +46,7 @@ pub fn run_command(comand: &str, args: &[&str]) {
 /// 
 /// Example:
 /// ```
-/// use cryptor_global::console;
+/// use ancryptor_global::console;
 /// console::out("hello");
 /// ```
 pub fn out(message: &str) {
@@ -42,12 +54,120 @@ pub fn out(message: &str) {
 }
 
 /// Prints a message to the standard output.
-/// 
+///
+/// Same as [`print_info`].
+///
 /// Example:
 /// ```
-/// use cryptor_global::console;
-/// console::out("hello");
+/// use ancryptor_global::console;
+/// console::print("hello".to_owned());
 /// ```
 pub fn print(message: String) {
-    println!("Message: {}", &message);
-}
\ No newline at end of file
+    print_info(message);
+}
+
+/// Prints an informational message, such as a success, to the standard output.
+///
+/// The message is green when the standard output is a terminal, unless
+/// `NO_COLOR` is set.
+///
+/// Example:
+/// ```
+/// use ancryptor_global::console;
+/// console::print_info("JNI Libs Published".to_owned());
+/// ```
+pub fn print_info(message: String) {
+    let stdout = io::stdout();
+    let color = use_color(stdout.is_terminal(), env::var_os(NO_COLOR_ENV_VAR)).then_some(GREEN);
+    write_message(&mut stdout.lock(), "Message", &message, color);
+}
+
+/// Prints a warning to the standard output, in yellow on a terminal.
+///
+/// Example:
+/// ```
+/// use ancryptor_global::console;
+/// console::print_warn("ANDROID_ABIS names no known target".to_owned());
+/// ```
+pub fn print_warn(message: String) {
+    let stdout = io::stdout();
+    let color = use_color(stdout.is_terminal(), env::var_os(NO_COLOR_ENV_VAR)).then_some(YELLOW);
+    write_message(&mut stdout.lock(), "Warning", &message, color);
+}
+
+/// Prints an error to the standard error, so CI can tell it apart
+/// from the regular output. The message is red when the standard error
+/// is a terminal, unless `NO_COLOR` is set.
+///
+/// Example:
+/// ```
+/// use ancryptor_global::console;
+///
+/// let result: Result<(), String> = Err("Error copying library file".to_owned());
+/// if let Err(error) = result {
+///     console::print_error(error);
+/// }
+/// ```
+pub fn print_error(message: String) {
+    let stderr = io::stderr();
+    let color = use_color(stderr.is_terminal(), env::var_os(NO_COLOR_ENV_VAR)).then_some(RED);
+    write_message(&mut stderr.lock(), "Error", &message, color);
+}
+
+///
+/// Colors are only worth it on a terminal: piped into a file or a CI log
+/// they would show up as raw escape codes.
+///
+fn use_color(is_terminal: bool, no_color: Option<OsString>) -> bool {
+    is_terminal && no_color.is_none_or(|no_color| no_color.is_empty())
+}
+
+fn write_message(writer: &mut impl Write, level: &str, message: &str, color: Option<&str>) {
+    // Like `println!`, except that a closed pipe is not worth a panic.
+    let _ = match color {
+        Some(color) => writeln!(writer, "{}{}: {}{}", color, level, message, RESET),
+        None => writeln!(writer, "{}: {}", level, message),
+    };
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_message_prefixes_the_level() {
+        let mut output = Vec::new();
+        write_message(&mut output, "Message", "hello", None);
+        write_message(&mut output, "Error", "Error copying library file", None);
+
+        assert_eq!(String::from_utf8(output).unwrap(), "Message: hello\nError: Error copying library file\n");
+    }
+
+    #[test]
+    fn test_piped_output_has_no_escape_codes() {
+        let mut output = Vec::new();
+        let color = use_color(false, None).then_some(GREEN);
+        write_message(&mut output, "Message", "hello", color);
+
+        assert!(!output.contains(&0x1b));
+        assert_eq!(String::from_utf8(output).unwrap(), "Message: hello\n");
+    }
+
+    #[test]
+    fn test_terminal_output_is_colored() {
+        let mut output = Vec::new();
+        let color = use_color(true, None).then_some(RED);
+        write_message(&mut output, "Error", "Error copying library file", color);
+
+        assert_eq!(String::from_utf8(output).unwrap(), "\x1b[31mError: Error copying library file\x1b[0m\n");
+    }
+
+    #[test]
+    fn test_no_color_disables_colors() {
+        assert!(!use_color(true, Some(OsString::from("1"))));
+        assert!(use_color(true, Some(OsString::new())));
+    }
+}
@@ -8,6 +8,8 @@ use std::fs::File;
 use std::io::Result;
 use std::io::ErrorKind;
 
+use sha2::{Digest, Sha256};
+
 ///
 /// Creates a cargo config file in the in the directory
 /// passed as a parameter.
@@ -41,9 +43,161 @@ fn create_config_file(file_path: &PathBuf) -> Result<File> {
 ///
 /// Copies the contents of one file to another.
 ///
+/// The copy is checked afterwards: if `to` does not end up the same size
+/// as `from`, e.g. because the disk filled up or the write was cut short,
+/// an `InvalidData` error is returned instead of a truncated file passing
+/// for a good one.
+///
 ///  - https://doc.rust-lang.org/std/fs/fn.copy.html
 ///
 pub fn copy_file(from: &str, to: &str)-> std::io::Result<()> {
     fs::copy(from, to)?;
+    verify_copy(from, to)
+}
+
+fn verify_copy(from: &str, to: &str) -> std::io::Result<()> {
+    let from_len = fs::metadata(from)?.len();
+    let to_len = fs::metadata(to)?.len();
+
+    if from_len != to_len {
+        let message = format!("Copy of {} is {} bytes, expected {}", from, to_len, from_len);
+        return Err(std::io::Error::new(ErrorKind::InvalidData, message));
+    }
+
     Ok(())
-}
\ No newline at end of file
+}
+
+///
+/// Moves a file, overwriting `to` if it exists.
+///
+/// Renames it when both paths are on the same filesystem, and otherwise
+/// copies it and then removes `from`.
+///
+///  - https://doc.rust-lang.org/std/fs/fn.rename.html
+///
+pub fn move_file(from: &str, to: &str) -> std::io::Result<()> {
+    match fs::rename(from, to) {
+        Err(error) if error.kind() == ErrorKind::CrossesDevices => {
+            copy_file(from, to)?;
+            fs::remove_file(from)
+        }
+        result => result,
+    }
+}
+
+///
+/// Deletes a file. A file that does not exist counts as deleted.
+///
+///  - https://doc.rust-lang.org/std/fs/fn.remove_file.html
+///
+pub fn delete_file(path: &str) -> std::io::Result<()> {
+    match fs::remove_file(path) {
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(()),
+        result => result,
+    }
+}
+
+///
+/// Returns the SHA-256 of the contents of a file, read in chunks so
+/// large libraries are not loaded into memory at once.
+///
+///  - https://docs.rs/sha2/latest/sha2/
+///
+pub fn sha256_file(path: &str) -> std::io::Result<[u8; 32]> {
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut File::open(path)?, &mut hasher)?;
+
+    Ok(hasher.finalize().into())
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_file_path(name: &str) -> String {
+        std::env::temp_dir()
+            .join(format!("ancryptor_global_{}_{}", std::process::id(), name))
+            .to_string_lossy()
+            .into_owned()
+    }
+
+    #[test]
+    fn test_copy_file_passes_verification() {
+        let from = temp_file_path("copy_from");
+        let to = temp_file_path("copy_to");
+        fs::write(&from, "new lib").unwrap();
+
+        copy_file(&from, &to).unwrap();
+
+        assert_eq!(fs::read_to_string(&to).unwrap(), "new lib");
+        fs::remove_file(&from).unwrap();
+        fs::remove_file(&to).unwrap();
+    }
+
+    #[test]
+    fn test_verify_copy_rejects_truncated_copy() {
+        let from = temp_file_path("verify_from");
+        let to = temp_file_path("verify_to");
+        fs::write(&from, "new lib").unwrap();
+        fs::write(&to, "new").unwrap();
+
+        let error = verify_copy(&from, &to).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::InvalidData);
+        fs::remove_file(&from).unwrap();
+        fs::remove_file(&to).unwrap();
+    }
+
+    #[test]
+    fn test_sha256_file_matches_known_digest() {
+        let path = temp_file_path("sha256");
+        fs::write(&path, "abc").unwrap();
+
+        let digest = sha256_file(&path).unwrap();
+
+        // SHA-256 of "abc", from FIPS 180-2.
+        assert_eq!(digest[..4], [0xba, 0x78, 0x16, 0xbf]);
+        assert_eq!(digest[28..], [0xf2, 0x00, 0x15, 0xad]);
+        assert_eq!(sha256_file(&temp_file_path("sha256_missing")).unwrap_err().kind(), ErrorKind::NotFound);
+        fs::remove_file(&path).unwrap();
+    }
+
+    #[test]
+    fn test_move_file_replaces_destination() {
+        let from = temp_file_path("move_from");
+        let to = temp_file_path("move_to");
+        fs::write(&from, "new lib").unwrap();
+        fs::write(&to, "stale lib").unwrap();
+
+        move_file(&from, &to).unwrap();
+
+        assert!(fs::metadata(&from).is_err());
+        assert_eq!(fs::read_to_string(&to).unwrap(), "new lib");
+        fs::remove_file(&to).unwrap();
+    }
+
+    #[test]
+    fn test_move_file_missing_source_fails() {
+        let error = move_file(&temp_file_path("move_missing"), &temp_file_path("move_missing_to")).unwrap_err();
+
+        assert_eq!(error.kind(), ErrorKind::NotFound);
+    }
+
+    #[test]
+    fn test_delete_file_removes_file() {
+        let path = temp_file_path("delete");
+        fs::write(&path, "stale lib").unwrap();
+
+        delete_file(&path).unwrap();
+
+        assert!(fs::metadata(&path).is_err());
+    }
+
+    #[test]
+    fn test_delete_file_missing_file_succeeds() {
+        assert!(delete_file(&temp_file_path("delete_missing")).is_ok());
+    }
+}
@@ -8,15 +8,15 @@
 #![allow(dead_code)]
 
 use std::env;
-use std::collections::HashMap;
-use std::io::Write;
+use std::fs;
+use std::collections::{BTreeMap, HashMap};
+use std::io::{ErrorKind, Write};
 
 use phf::phf_map;
 
-use serde::Serialize;
-use toml;
+use serde::{Deserialize, Serialize};
 
-use std::path::MAIN_SEPARATOR_STR;
+use std::path::{MAIN_SEPARATOR_STR, PathBuf};
 
 use ancryptor_global::io;
 use ancryptor_global::console;
@@ -37,6 +37,9 @@ use ancryptor_global::system;
 // -----------------------------------------------------------------------------------------------
 static ANDROID_NDK_VERSION: &str = "25.1.8937393";
 
+// Replaces `ANDROID_TARGET_ABI_CONFIG` when present at the crate root
+static TARGETS_CONFIG_FILE_NAME: &str = "targets.toml";
+
 ///
 /// Due to Rust limitations on generating a static Map with a custom type, a tuple is
 /// needed with the following value representation:
@@ -76,6 +79,102 @@ pub static ANDROID_TARGET_ABI_CONFIG: phf::Map<&'static str, (&'static str, &'st
     "x86_64-linux-android" => ("x86_64-linux-android-ar", "x86_64-linux-android21-clang", "x86_64"),
 };
 
+///
+/// The iOS counterpart of `ANDROID_TARGET_ABI_CONFIG`: each iOS target maps to the
+/// slice (folder) of the iOS project's framework directory its static library goes in.
+/// The names follow the XCFramework slice identifiers:
+///
+/// ```
+/// -------------------------------------------------------------------------------------
+///  IOS TARGET                    SLICE (folder in the iOS project inside `Frameworks`)
+///  ------------------------------------------------------------------------------------
+///  aarch64-apple-ios        ---> ios-arm64
+///  aarch64-apple-ios-sim    ---> ios-arm64-simulator
+///  x86_64-apple-ios         ---> ios-x86_64-simulator
+/// -------------------------------------------------------------------------------------
+/// ```
+///
+/// iOS targets are built on macOS with the Xcode toolchain, so they need no entry in
+/// `.cargo/config` and are not added to the toolchain here.
+///
+pub static IOS_TARGET_CONFIG: phf::Map<&'static str, &'static str> = phf_map! {
+    "aarch64-apple-ios" => "ios-arm64",
+    "aarch64-apple-ios-sim" => "ios-arm64-simulator",
+    "x86_64-apple-ios" => "ios-x86_64-simulator",
+};
+
+///
+/// One android target of the configuration, the named counterpart of
+/// the `ANDROID_TARGET_ABI_CONFIG` tuple. In `targets.toml` each target
+/// is a table, like so:
+///
+/// ```toml
+/// [target.aarch64-linux-android]
+/// ar = "aarch64-linux-android-ar"
+/// linker = "aarch64-linux-android21-clang"
+/// abi = "arm64-v8a"
+/// ```
+///
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct AndroidTargetAbi {
+    // Archiver, Tuple.0.
+    pub ar: String,
+    // Linker, Tuple.1.
+    pub linker: String,
+    // ABI folder in the android project inside `jniLibs`, Tuple.2.
+    pub abi: String,
+}
+
+#[derive(Deserialize)]
+struct AndroidTargetsFile {
+    #[serde(rename = "target")]
+    targets: BTreeMap<String, AndroidTargetAbi>,
+}
+
+///
+/// Parses the contents of a `targets.toml` file into a map of
+/// each android target to its configuration.
+///
+pub fn parse_android_target_abi_config(contents: &str) -> Result<BTreeMap<String, AndroidTargetAbi>, String> {
+    toml::from_str::<AndroidTargetsFile>(contents)
+        .map(|targets_file| targets_file.targets)
+        .map_err(|error| format!("Cannot parse {}: {}", TARGETS_CONFIG_FILE_NAME, error))
+}
+
+///
+/// Returns the android targets to build and publish: the ones listed in
+/// `targets.toml` at the crate root, so ABIs can be changed without
+/// touching code, or `ANDROID_TARGET_ABI_CONFIG` when there is no such file.
+///
+pub fn android_target_abi_config() -> Result<BTreeMap<String, AndroidTargetAbi>, String> {
+    match fs::read_to_string(targets_config_file_path()) {
+        Ok(contents) => parse_android_target_abi_config(&contents),
+        Err(error) if error.kind() == ErrorKind::NotFound => Ok(default_android_target_abi_config()),
+        Err(error) => Err(format!("Cannot read {}: {}", TARGETS_CONFIG_FILE_NAME, error)),
+    }
+}
+
+///
+/// Returns `ANDROID_TARGET_ABI_CONFIG` as a map of named configurations.
+///
+pub fn default_android_target_abi_config() -> BTreeMap<String, AndroidTargetAbi> {
+    ANDROID_TARGET_ABI_CONFIG.entries()
+        .map(|(target, (ar, linker, abi))| {
+            let target_abi = AndroidTargetAbi {
+                ar: ar.to_string(),
+                linker: linker.to_string(),
+                abi: abi.to_string(),
+            };
+
+            (target.to_string(), target_abi)
+        })
+        .collect()
+}
+
+fn targets_config_file_path() -> PathBuf {
+    PathBuf::from(env!("CARGO_MANIFEST_DIR")).join(TARGETS_CONFIG_FILE_NAME)
+}
+
 ///
 /// This returns the android tool chain path
 /// based on current host operating system.
@@ -116,7 +215,7 @@ fn android_tool_chain_path() -> String {
     android_tool_chain.push_str(MAIN_SEPARATOR_STR);
     android_tool_chain.push_str("prebuilt");
     android_tool_chain.push_str(MAIN_SEPARATOR_STR);
-    android_tool_chain.push_str(&android_ndk_host_tag);
+    android_tool_chain.push_str(android_ndk_host_tag);
     android_tool_chain.push_str(MAIN_SEPARATOR_STR);
     android_tool_chain.push_str("bin");
     android_tool_chain.push_str(MAIN_SEPARATOR_STR);
@@ -177,15 +276,15 @@ fn build_linker(linker_path: &str) -> String {
     )
 }
 
-fn android_targets<'a>() -> AndroidTargets<'a> {
+fn android_targets(target_abi_config: &BTreeMap<String, AndroidTargetAbi>) -> AndroidTargets<'_> {
     let mut android_targets = AndroidTargets {
-        targets: HashMap::with_capacity(ANDROID_TARGET_ABI_CONFIG.len())
+        targets: HashMap::with_capacity(target_abi_config.len())
     };
 
-    for (target, config) in ANDROID_TARGET_ABI_CONFIG.entries() {
+    for (target, config) in target_abi_config {
         let target_config = AndroidTargetConfig {
-            ar: build_archiver(config.0),
-            linker: build_linker(config.1)
+            ar: build_archiver(&config.ar),
+            linker: build_linker(&config.linker)
         };
 
         android_targets.targets.insert(target, target_config);
@@ -207,21 +306,21 @@ fn android_targets<'a>() -> AndroidTargets<'a> {
 /// [target.armv7-linux-androideabi]
 /// ar = "$ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/arm-linux-androideabi-ar"
 /// linker = "$ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/armv7a-linux-androideabi21-clang"
-
+///
 /// [target.aarch64-linux-android]
 /// ar = "$ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/aarch64-linux-android-ar"
 /// linker = "ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/aarch64-linux-android21-clang"
-
+///
 /// [target.i686-linux-android]
 /// ar = "$ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/i686-linux-android-ar"
 /// linker = "$ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/i686-linux-android21-clang"
-
+///
 /// [target.x86_64-linux-android]
 /// ar = "$ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/x86_64-linux-android-ar"
 /// linker = "$ANDROID_HOME/ndk/25.1.8937393/toolchains/llvm/prebuilt/linux-x86_64/bin/x86_64-linux-android21-clang"
 /// ```
-fn create_android_targets_config_file() {
-    let targets_config = android_targets();
+fn create_android_targets_config_file(target_abi_config: &BTreeMap<String, AndroidTargetAbi>) {
+    let targets_config = android_targets(target_abi_config);
     let mut config_file = io::create_cargo_config_file(&env::current_dir().unwrap());
     let toml = toml::to_string(&targets_config).unwrap();
 
@@ -240,23 +339,29 @@ fn create_android_targets_config_file() {
 /// Cross-compilation:
 ///  - https://rust-lang.github.io/rustup/cross-compilation.html
 ///
-fn add_android_targets_to_toolchain() {
+fn add_android_targets_to_toolchain(target_abi_config: &BTreeMap<String, AndroidTargetAbi>) {
     let mut command_args = Vec::new();
     command_args.push("target");
     command_args.push("add");
 
-    for target in ANDROID_TARGET_ABI_CONFIG.keys() {
+    for target in target_abi_config.keys() {
         command_args.push(target)
     }
 
-    console::run_command("rustup", &command_args.as_slice());
+    console::run_command("rustup", command_args.as_slice());
 }
 
 fn main() {
     system::rerun_if_changed("build.rs");
+    // A missing file would count as changed and rerun this script on every build.
+    if targets_config_file_path().exists() {
+        system::rerun_if_changed(TARGETS_CONFIG_FILE_NAME);
+    }
+
+    let target_abi_config = android_target_abi_config().unwrap_or_else(|error| panic!("{}", error));
 
-    create_android_targets_config_file();
-    add_android_targets_to_toolchain();
+    create_android_targets_config_file(&target_abi_config);
+    add_android_targets_to_toolchain(&target_abi_config);
 }
 
 //
@@ -268,7 +373,8 @@ mod tests {
 
     #[test]
     fn android_targets_and_config_same_size() {
-        let android_targets = android_targets().targets;
+        let target_abi_config = default_android_target_abi_config();
+        let android_targets = android_targets(&target_abi_config).targets;
         assert_eq!(&android_targets.len() , &ANDROID_TARGET_ABI_CONFIG.len());
     }
 
@@ -284,4 +390,47 @@ mod tests {
             if target_config_key.starts_with("x86_64") { assert_eq!(target_config_abi, "x86_64") }
         }
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn parse_android_target_abi_config_reads_every_target() {
+        let contents = r#"
+            [target.aarch64-linux-android]
+            ar = "aarch64-linux-android-ar"
+            linker = "aarch64-linux-android21-clang"
+            abi = "arm64-v8a"
+
+            [target.x86_64-linux-android]
+            ar = "x86_64-linux-android-ar"
+            linker = "x86_64-linux-android21-clang"
+            abi = "x86_64"
+        "#;
+        let target_abi_config = parse_android_target_abi_config(contents).unwrap();
+
+        assert_eq!(target_abi_config.len(), 2);
+        assert_eq!(target_abi_config["aarch64-linux-android"], AndroidTargetAbi {
+            ar: "aarch64-linux-android-ar".to_owned(),
+            linker: "aarch64-linux-android21-clang".to_owned(),
+            abi: "arm64-v8a".to_owned(),
+        });
+        assert_eq!(target_abi_config["x86_64-linux-android"].abi, "x86_64");
+    }
+
+    #[test]
+    fn parse_android_target_abi_config_rejects_incomplete_targets() {
+        let contents = r#"
+            [target.aarch64-linux-android]
+            abi = "arm64-v8a"
+        "#;
+
+        assert!(parse_android_target_abi_config(contents).is_err());
+    }
+
+    #[test]
+    fn default_android_target_abi_config_matches_static_config() {
+        let target_abi_config = default_android_target_abi_config();
+
+        assert_eq!(target_abi_config.len(), ANDROID_TARGET_ABI_CONFIG.len());
+        assert_eq!(target_abi_config["armv7-linux-androideabi"].abi, "armeabi-v7a");
+        assert_eq!(target_abi_config["i686-linux-android"].linker, "i686-linux-android21-clang");
+    }
+}
@@ -0,0 +1,158 @@
+//!
+//! This is a binary targets, which is an executable program
+//! that can be run after crate compilation.
+//!
+//! It will basically build the release version of this crate
+//! for each android target, stopping at the first one that
+//! fails, so `publish` can run right after it.
+//!
+//! ## Examples
+//! ```
+//! $ cd cryptor_jni/
+//! $ cargo run --bin build_all && cargo run --bin publish
+//! ```
+//!
+//! The android targets come from `targets.toml` at the crate root when
+//! there is one, and from `ANDROID_TARGET_ABI_CONFIG` in `build.rs`
+//! otherwise. The output of `cargo` is streamed as it builds.
+//!
+//! For more information, refer to the official doc:
+//!  - https://doc.rust-lang.org/cargo/reference/cargo-targets.html#binaries
+//!
+
+
+// https://doc.rust-lang.org/reference/items/modules.html
+#[path="../../build.rs"]
+mod build;
+
+use std::process::{Command, ExitCode};
+
+use ancryptor_global::console;
+
+///
+/// Returns the `cargo` arguments that build the release version
+/// of this crate for `target`.
+///
+/// ## Arguments
+///
+/// * `target` - A string slice that holds the target triple.
+///
+/// ## Examples
+///
+/// `$ cargo build --release --target aarch64-linux-android`
+///
+fn build_command(target: &str) -> Vec<String> {
+    ["build", "--release", "--target", target].map(str::to_owned).to_vec()
+}
+
+///
+/// Runs `cargo` with `command_args`, its output going straight
+/// to this process' output.
+///
+fn run_cargo(command_args: &[String]) -> Result<(), String> {
+    let status = Command::new("cargo")
+        .args(command_args)
+        .status()
+        .map_err(|error| format!("Failed to execute cargo: {}", error))?;
+
+    if !status.success() {
+        return Err(format!("cargo {} failed with {}", command_args.join(" "), status));
+    }
+
+    Ok(())
+}
+
+///
+/// Builds each of `targets` in order with `run`, stopping at the first
+/// failure, and returns the targets that were built.
+///
+/// ## Arguments
+///
+/// * `targets` - The target triples to build.
+/// * `run` - Runs `cargo` with the given arguments.
+///
+fn build_targets<F>(targets: &[String], mut run: F) -> Result<Vec<String>, String>
+where
+    F: FnMut(&[String]) -> Result<(), String>,
+{
+    let mut built_targets = Vec::new();
+
+    for target in targets {
+        console::print_info(format!("Building Android Target --> {}", target));
+
+        if let Err(error) = run(&build_command(target)) {
+            let built = if built_targets.is_empty() { "none".to_owned() } else { built_targets.join(", ") };
+            return Err(format!("{} (built: {})", error, built));
+        }
+
+        built_targets.push(target.to_owned());
+    }
+
+    Ok(built_targets)
+}
+
+// Public so that the `release` binary can run it too.
+pub fn main() -> ExitCode {
+    let result = build::android_target_abi_config()
+        .and_then(|target_abi_config| {
+            let targets: Vec<String> = target_abi_config.into_keys().collect();
+            build_targets(&targets, run_cargo)
+        });
+
+    match result {
+        Ok(built_targets) => {
+            console::print_info(format!("Built Android Targets: {}", built_targets.join(", ")));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            console::print_error(error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn build_command_builds_release_for_target() {
+        assert_eq!(build_command("aarch64-linux-android"), vec!["build", "--release", "--target", "aarch64-linux-android"]);
+    }
+
+    #[test]
+    fn build_targets_builds_every_target_in_order() {
+        let targets = ["aarch64-linux-android", "x86_64-linux-android"].map(str::to_owned);
+        let mut commands = Vec::new();
+
+        let built_targets = build_targets(&targets, |command_args| {
+            commands.push(command_args.join(" "));
+            Ok(())
+        });
+
+        assert_eq!(built_targets, Ok(targets.to_vec()));
+        assert_eq!(commands, vec![
+            "build --release --target aarch64-linux-android",
+            "build --release --target x86_64-linux-android",
+        ]);
+    }
+
+    #[test]
+    fn build_targets_stops_at_first_failure() {
+        let targets = ["aarch64-linux-android", "i686-linux-android", "x86_64-linux-android"].map(str::to_owned);
+        let mut runs = 0;
+
+        let error = build_targets(&targets, |command_args| {
+            runs += 1;
+            if command_args.contains(&"i686-linux-android".to_owned()) {
+                return Err("cargo build failed".to_owned());
+            }
+
+            Ok(())
+        }).unwrap_err();
+
+        assert_eq!(runs, 2);
+        assert_eq!(error, "cargo build failed (built: aarch64-linux-android)");
+    }
+}
@@ -0,0 +1,121 @@
+//!
+//! This is a binary targets, which is an executable program
+//! that can be run after crate compilation.
+//!
+//! It will basically remove the libraries previously published
+//! to the android project, e.g. after switching ABIs or renaming
+//! the library, so stale ones are not packaged into the APK.
+//!
+//! ## Examples
+//! ```
+//! $ cd cryptor_jni/
+//! $ cargo run --bin clean
+//! ```
+//!
+//! For more information, refer to the official doc:
+//!  - https://doc.rust-lang.org/cargo/reference/cargo-targets.html#binaries
+//!
+
+
+// https://doc.rust-lang.org/reference/items/modules.html
+#[path="../../build.rs"]
+mod build;
+#[path="../paths.rs"]
+mod paths;
+
+use std::error::Error;
+use std::path::PathBuf;
+use std::process::ExitCode;
+
+use ancryptor_global::{
+    console,
+    io,
+};
+
+use paths::{
+    DEFAULT_ANDROID_PROJECT,
+    android_jni_dir_path,
+};
+
+///
+/// Deletes each of `lib_file_paths` and returns how many of them
+/// existed, skipping the ones that were never published.
+///
+/// ## Arguments
+///
+/// * `lib_file_paths` - The paths of the published libraries.
+///
+fn delete_lib_files(lib_file_paths: &[String]) -> std::io::Result<usize> {
+    let mut removed = 0;
+
+    for lib_file_path in lib_file_paths {
+        if PathBuf::from(lib_file_path).is_file() {
+            removed += 1;
+        }
+        io::delete_file(lib_file_path)?;
+    }
+
+    Ok(removed)
+}
+
+///
+/// Removes the library published for each configured android
+/// target from the default android project.
+///
+fn clean_android_project() -> Result<usize, Box<dyn Error>> {
+    let android_lib_file_paths: Vec<String> = build::android_target_abi_config()?.values()
+        .map(|target_abi| android_jni_dir_path(DEFAULT_ANDROID_PROJECT, &target_abi.abi))
+        .collect();
+
+    Ok(delete_lib_files(&android_lib_file_paths)?)
+}
+
+fn main() -> ExitCode {
+    match clean_android_project() {
+        Ok(removed) => {
+            console::print_info(format!("Removed {} JNI Lib(s) from the Android Project", removed));
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            console::print_error(error.to_string());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::fs;
+
+    #[test]
+    fn delete_lib_files_counts_removed_files() {
+        let jni_libs_dir = env::temp_dir().join(format!("clean-jni-libs-{}", std::process::id()));
+        let lib_file_paths: Vec<String> = ["arm64-v8a", "x86", "x86_64"].iter()
+            .map(|folder| jni_libs_dir.join(folder).join(paths::JNI_LIB_FILE_NAME).to_str().unwrap().to_owned())
+            .collect();
+
+        // Only two of the three targets were ever published.
+        for lib_file_path in &lib_file_paths[..2] {
+            let lib_file_path = PathBuf::from(lib_file_path);
+            fs::create_dir_all(lib_file_path.parent().unwrap()).unwrap();
+            fs::write(&lib_file_path, "stale lib").unwrap();
+        }
+
+        assert_eq!(delete_lib_files(&lib_file_paths).unwrap(), 2);
+        assert!(lib_file_paths.iter().all(|lib_file_path| !PathBuf::from(lib_file_path).exists()));
+
+        fs::remove_dir_all(jni_libs_dir).unwrap();
+    }
+
+    #[test]
+    fn delete_lib_files_without_published_files_removes_nothing() {
+        let lib_file_path = env::temp_dir().join(format!("clean-never-published-{}.so", std::process::id()));
+
+        assert_eq!(delete_lib_files(&[lib_file_path.to_str().unwrap().to_owned()]).unwrap(), 0);
+        assert_eq!(delete_lib_files(&[]).unwrap(), 0);
+    }
+}
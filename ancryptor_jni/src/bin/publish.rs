@@ -1,163 +1,435 @@
-///
-/// This is a binary targets, which is an executable program
-/// that can be run after crate compilation.
-///
-/// It will basically copy the 'release' version
-/// of this crate to the corresponding android
-/// project.
-///
-/// ## Examples
-/// ```
-/// $ cd cryptor_jni/
-/// $ cargo run --bin publish
-/// ```
-///
-/// For more information, refer to the official doc:
-///  - https://doc.rust-lang.org/cargo/reference/cargo-targets.html#binaries
-///
+//!
+//! This is a binary targets, which is an executable program
+//! that can be run after crate compilation.
+//!
+//! It will basically copy the 'release' version
+//! of this crate to the corresponding android
+//! project, or with `--ios` to the iOS project.
+//!
+//! ## Examples
+//! ```
+//! $ cd cryptor_jni/
+//! $ cargo run --bin publish
+//! $ ANDROID_ABIS=aarch64-linux-android cargo run --bin publish
+//! $ cargo run --bin publish -- --target aarch64-linux-android
+//! $ cargo run --bin publish -- --dry-run
+//! $ cargo run --bin publish -- --android-project android-sample --android-project wallet-app
+//! $ cargo run --bin publish -- --ios --target aarch64-apple-ios
+//! ```
+//!
+//! Set `ANDROID_ABIS` to a comma-separated list of target triples to
+//! only publish those, e.g. the ABI of the device at hand. One or more
+//! `--target <triple>` arguments do the same and take precedence over
+//! `ANDROID_ABIS`; unlike it, they fail on a target that is not known.
+//!
+//! Libraries go to the `android-sample` project next to this repository.
+//! One or more `--android-project <path>` arguments publish to those
+//! projects instead, each either absolute or relative to the directory
+//! holding this repository.
+//!
+//! `--dry-run` prints what would be copied where, and whether each
+//! library exists yet, without touching the android project.
+//!
+//! Each android publish also writes `jniLibs.sha256` into the jniLibs
+//! directory, listing the SHA-256 of every library it copied, so CI can
+//! check them with `sha256sum -c jniLibs.sha256`.
+//!
+//! The android targets come from `targets.toml` at the crate root when
+//! there is one, and from `ANDROID_TARGET_ABI_CONFIG` in `build.rs`
+//! otherwise.
+//!
+//! `--ios` publishes the static library of each iOS target instead, into
+//! the `Frameworks` directory of the iOS project. `--target` then names
+//! iOS targets, and `ANDROID_ABIS` is ignored.
+//!
+//! For more information, refer to the official doc:
+//!  - https://doc.rust-lang.org/cargo/reference/cargo-targets.html#binaries
+//!
 
 
 // https://doc.rust-lang.org/reference/items/modules.html
 #[path="../../build.rs"]
 mod build;
+#[path="../paths.rs"]
+mod paths;
 
+use std::collections::BTreeMap;
 use std::env;
 use std::error::Error;
+use std::fs;
 use std::path::PathBuf;
-use std::path::MAIN_SEPARATOR_STR;
+use std::process::ExitCode;
+use std::thread;
+
+use ancryptor::encode_hex;
 
 use ancryptor_global::{
     console,
     io,
 };
 
-// Represents the crate/lib file name generated
-static JNI_LIB_FILE_NAME: &str = "libcryptor_jni.so";
+use paths::{
+    DEFAULT_ANDROID_PROJECT,
+    Platform,
+    lib_file_name,
+    project_dir_path,
+    android_jni_libs_dir_path,
+    android_jni_dir_path,
+    ios_framework_dir_path,
+    crate_file_path_for_target,
+};
+
+// The `sha256sum` manifest of the published libs, in the jniLibs directory
+static JNI_LIBS_MANIFEST_FILE_NAME: &str = "jniLibs.sha256";
+
+// Comma-separated target triples to restrict publishing to
+static ANDROID_ABIS_ENV_VAR: &str = "ANDROID_ABIS";
 
 ///
-/// Returns the project directory path.
+/// Returns the android targets to publish: the target triples listed in
+/// `requested_abis` that are also in `target_abi_config`, or every
+/// configured target when nothing was requested.
 ///
-/// ## Examples
+/// ## Arguments
 ///
-/// `$ rust-library/`
+/// * `target_abi_config` - The configured android targets.
+/// * `requested_abis` - The value of `ANDROID_ABIS`, if set.
 ///
-fn project_dir_path() -> String {
-    let current_dir_path = env::current_dir().expect(
-        "Cannot read current directory"
-    );
-    let target_dir_path = current_dir_path.parent().expect(
-        "Cannot find/read 'rust-library' directory"
-    );
+fn android_targets_to_publish(target_abi_config: &BTreeMap<String, build::AndroidTargetAbi>, requested_abis: Option<String>) -> Vec<String> {
+    let known_targets = target_abi_config.keys().cloned();
 
-    target_dir_path.as_os_str().to_str().expect(
-        "Cannot validate 'rust-library' directory"
-    ).to_owned()
+    match requested_abis {
+        None => known_targets.collect(),
+        Some(requested_abis) => {
+            let requested_targets: Vec<&str> = requested_abis.split(',').map(str::trim).collect();
+            known_targets.filter(|target| requested_targets.contains(&target.as_str())).collect()
+        }
+    }
+}
+
+///
+/// What to publish and how, as given on the command line.
+///
+#[derive(Debug, Default, PartialEq)]
+struct PublishOptions {
+    // The targets given with `--target`, in order; empty means all.
+    targets: Vec<String>,
+    // Only print what would be copied.
+    dry_run: bool,
+    // Where to publish, Android unless `--ios` is given.
+    platform: Platform,
+    // The projects given with `--android-project`, in order; empty means `DEFAULT_ANDROID_PROJECT`.
+    android_projects: Vec<String>,
 }
 
 ///
-/// Returns the jni directory path in the android project
-/// where the release version of this crate should be
-/// placed.
+/// Parses the arguments that follow the binary name.
 ///
 /// ## Arguments
 ///
-/// * `android_jni_lib_folder` - A string slice that holds the name of the android target.
-///
-/// ## Examples
-///
-/// `$ android-sample/app/src/main/jniLibs`
-///
-fn android_jni_dir_path(android_jni_lib_folder: &str) -> String {
-    let project_dir = PathBuf::from(project_dir_path());
-    let android_project_dir_path = project_dir.parent().expect(
-        "Cannot find/read 'android-sample' directory"
-    );
-
-    let mut android_jni_file_path = android_project_dir_path.as_os_str().to_str().expect(
-        "Cannot validate 'android-sample' directory"
-    ).to_owned();
-
-    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
-    android_jni_file_path.push_str("android-sample");
-    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
-    android_jni_file_path.push_str("app");
-    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
-    android_jni_file_path.push_str("src");
-    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
-    android_jni_file_path.push_str("main");
-    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
-    android_jni_file_path.push_str("jniLibs");
-    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
-    android_jni_file_path.push_str(&android_jni_lib_folder);
-    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
-    android_jni_file_path.push_str(JNI_LIB_FILE_NAME);
-
-    android_jni_file_path
+/// * `args` - The command line arguments, without the binary name.
+///
+fn parse_publish_args(args: &[String]) -> Result<PublishOptions, String> {
+    let mut options = PublishOptions::default();
+    let mut args = args.iter();
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--target" => {
+                let target = args.next().ok_or("--target needs a target triple")?;
+                options.targets.push(target.to_owned());
+            }
+            "--dry-run" => options.dry_run = true,
+            "--ios" => options.platform = Platform::Ios,
+            "--android-project" => {
+                let android_project = args.next().ok_or("--android-project needs a path")?;
+                options.android_projects.push(android_project.to_owned());
+            }
+            unknown => return Err(format!("Unknown argument '{}'", unknown)),
+        }
+    }
+
+    if options.platform == Platform::Ios && !options.android_projects.is_empty() {
+        return Err("--android-project does not apply to --ios".to_owned())
+    }
+
+    Ok(options)
 }
 
 ///
-/// Returns the file path where the
-/// release version of this crate
-/// is placed.
+/// Copies one library file into the android project or, on a dry run,
+/// only prints where it would go and whether it exists.
+///
+/// Missing destination directories, e.g. `jniLibs/arm64-v8a` in a fresh
+/// android module, are created first.
 ///
 /// ## Arguments
 ///
-/// * `project_dir_path` - A string slice that holds thsi project directory path.
-/// * `android_target` - A string slice that holds the name of the android target.
+/// * `crate_lib_file_path` - A string slice that holds the path of the built library.
+/// * `android_lib_file_path` - A string slice that holds its destination in the android project.
+/// * `dry_run` - Whether to leave the android project untouched.
 ///
-/// ## Examples
+fn publish_lib_file(crate_lib_file_path: &str, android_lib_file_path: &str, dry_run: bool) -> Result<(), Box<dyn Error>> {
+    let crate_lib_file_exists = PathBuf::from(crate_lib_file_path).exists();
+
+    if dry_run {
+        let status = if crate_lib_file_exists { "exists" } else { "missing" };
+        console::print(format!("{} ({}) -> {}", crate_lib_file_path, status, android_lib_file_path));
+        return Ok(())
+    }
+
+    if crate_lib_file_exists {
+        if let Some(android_lib_dir_path) = PathBuf::from(android_lib_file_path).parent() {
+            fs::create_dir_all(android_lib_dir_path)?;
+        }
+        io::copy_file(crate_lib_file_path, android_lib_file_path)?;
+    } else {
+        return Err("Error copying library file".into())
+    }
+
+    Ok(())
+}
+
 ///
-/// `$ rust-library/target/x86_64-linux-android/release/JNI_LIB_FILE_NAME`
+/// Returns the jniLibs folder (ABI) of a configured android target.
 ///
-fn crate_file_path_for_target(project_dir_path: &str, android_target: &str) -> String {
-    let mut crate_lib_file_path = project_dir_path.to_owned();
+/// ## Arguments
+///
+/// * `android_target` - A string slice that holds the target triple.
+///
+fn android_jni_lib_folder_for(android_target: &str) -> Result<String, Box<dyn Error>> {
+    let target_abi_config = build::android_target_abi_config()?;
+    let target_abi = target_abi_config.get(android_target).ok_or(
+        format!("Unknown android target '{}'", android_target)
+    )?;
+
+    Ok(target_abi.abi.to_owned())
+}
 
-    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
-    crate_lib_file_path.push_str("target");
-    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
-    crate_lib_file_path.push_str(&android_target);
-    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
-    crate_lib_file_path.push_str("release");
-    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
-    crate_lib_file_path.push_str(JNI_LIB_FILE_NAME);
+///
+/// Copy the release version of one target to the corresponding
+/// directory in the android or iOS project.
+///
+/// ## Arguments
+///
+/// * `target` - A string slice that holds the target triple, which must be a configured android target or in `IOS_TARGET_CONFIG`, depending on `platform`.
+/// * `platform` - The platform to publish to.
+/// * `android_project` - A string slice that holds the android project to publish to, unused for iOS.
+/// * `dry_run` - Whether to only print what would be copied.
+///
+fn publish_single_target(target: &str, platform: Platform, android_project: &str, dry_run: bool) -> Result<(), Box<dyn Error>> {
+    // build the destination path based on the current target
+    let project_lib_file_path = match platform {
+        Platform::Android => {
+            // get the jni android folder name to place our 'JNI_LIB_FILE_NAME' file.
+            let android_jni_lib_folder = android_jni_lib_folder_for(target)?;
 
-    crate_lib_file_path
+            android_jni_dir_path(android_project, &android_jni_lib_folder)
+        }
+        Platform::Ios => {
+            // get the framework slice name to place our 'IOS_LIB_FILE_NAME' file.
+            let ios_framework_folder = build::IOS_TARGET_CONFIG.get(target).ok_or(
+                format!("Unknown iOS target '{}'", target)
+            )?;
+
+            ios_framework_dir_path(ios_framework_folder)
+        }
+    };
+
+    // get the path of the library file.
+    let crate_lib_file_path = crate_file_path_for_target(&project_dir_path(), target, platform);
+
+    publish_lib_file(&crate_lib_file_path, &project_lib_file_path, dry_run)
 }
 
 ///
-/// Copy the release version of each android target to the corresponding
-/// directory in the android project.
+/// Returns the manifest entry of one android target: the hex SHA-256 of
+/// its release library, and the path of the published copy relative to
+/// the jniLibs directory.
 ///
-fn publish_jni_lib_to_android_project() -> Result<String, Box<dyn Error>> {
-    let project_dir_path = project_dir_path();
+/// ## Arguments
+///
+/// * `android_target` - A string slice that holds the target triple, which must be configured.
+///
+fn manifest_entry(android_target: &str) -> Result<(String, String), Box<dyn Error>> {
+    let android_jni_lib_folder = android_jni_lib_folder_for(android_target)?;
 
-    // we loop through all android targets
-    for android_target in build::ANDROID_TARGET_ABI_CONFIG.keys() {
-        // get the path of the 'JNI_LIB_FILE_NAME' file.
-        let crate_lib_file_path = crate_file_path_for_target(&project_dir_path, &android_target);
+    let crate_lib_file_path = crate_file_path_for_target(&project_dir_path(), android_target, Platform::Android);
+    let digest = encode_hex(&io::sha256_file(&crate_lib_file_path)?);
 
-        // get the jni android folder name to place our 'JNI_LIB_FILE_NAME' file.
-        let android_jni_lib_folder = build::ANDROID_TARGET_ABI_CONFIG.get(&android_target).expect(
-            "Cannot find 'jniLib' folder in 'android-sample' project."
-        ).2;
+    // `sha256sum` paths always use '/', whatever the host.
+    Ok((digest, format!("{}/{}", android_jni_lib_folder, lib_file_name(Platform::Android))))
+}
 
-        // build the entire jniLib based on the current android target
-        let android_lib_file_path = android_jni_dir_path(&android_jni_lib_folder);
+///
+/// Returns the manifest for `entries` in the `sha256sum` format: one
+/// `<hex-sha256>  <relative-path>` line per entry.
+///
+/// ## Arguments
+///
+/// * `entries` - The hex digest and relative path of each published library.
+///
+fn manifest_contents(entries: &[(String, String)]) -> String {
+    entries.iter()
+        .map(|(digest, relative_path)| format!("{}  {}\n", digest, relative_path))
+        .collect()
+}
+
+///
+/// Writes the `JNI_LIBS_MANIFEST_FILE_NAME` manifest of the published
+/// libraries into the jniLibs directory of `android_project`, replacing
+/// any previous one, so `sha256sum -c jniLibs.sha256` can be run from there.
+///
+/// ## Arguments
+///
+/// * `android_project` - A string slice that holds the android project the libraries were published to.
+/// * `entries` - The hex digest and relative path of each published library.
+///
+fn write_manifest(android_project: &str, entries: &[(String, String)]) -> std::io::Result<()> {
+    let manifest_file_path = PathBuf::from(android_jni_libs_dir_path(android_project)).join(JNI_LIBS_MANIFEST_FILE_NAME);
 
-        if PathBuf::from(&crate_lib_file_path).exists() {
-            io::copy_file(&crate_lib_file_path, &android_lib_file_path)?;
-        } else {
-            return Err("Error copying library file".into())
+    fs::write(manifest_file_path, manifest_contents(entries))
+}
+
+///
+/// Runs `publish` for each of `targets` on a thread of its own, so large
+/// libraries are copied side by side, and waits for all of them.
+///
+/// Fails if any target failed, with the error of every failing target,
+/// once the others are done.
+///
+/// ## Arguments
+///
+/// * `targets` - The target triples to publish.
+/// * `publish` - Publishes one target.
+///
+fn publish_targets_in_parallel<F>(targets: &[String], publish: F) -> Result<(), Box<dyn Error>>
+where
+    F: Fn(&str) -> Result<(), Box<dyn Error>> + Sync,
+{
+    let publish = &publish;
+    let errors: Vec<String> = thread::scope(|scope| {
+        let handles: Vec<_> = targets.iter()
+            .map(|target| scope.spawn(move || {
+                // `Box<dyn Error>` cannot leave the thread, its message can.
+                publish(target).map_err(|error| format!("{}: {}", target, error))
+            }))
+            .collect();
+
+        handles.into_iter()
+            .zip(targets)
+            .filter_map(|(handle, target)| match handle.join() {
+                Ok(result) => result.err(),
+                Err(_) => Some(format!("{}: publishing panicked", target)),
+            })
+            .collect()
+    });
+
+    if !errors.is_empty() {
+        return Err(errors.join("\n").into())
+    }
+
+    Ok(())
+}
+
+///
+/// Copy the release version of each selected android target to the
+/// corresponding directory in each android project, then write the
+/// checksum manifest of what was copied into each of them.
+///
+/// ## Arguments
+///
+/// * `options` - The targets given with `--target`, where none means `ANDROID_ABIS` or every known target, the android projects, and whether this is a dry run.
+///
+fn publish_jni_lib_to_android_project(options: PublishOptions) -> Result<String, Box<dyn Error>> {
+    let android_targets = if options.targets.is_empty() {
+        let target_abi_config = build::android_target_abi_config()?;
+        let android_targets = android_targets_to_publish(&target_abi_config, env::var(ANDROID_ABIS_ENV_VAR).ok());
+        if android_targets.is_empty() {
+            return Err(format!("No known android target in '{}'", ANDROID_ABIS_ENV_VAR).into())
         }
+
+        android_targets
+    } else {
+        options.targets
+    };
+
+    let android_projects = if options.android_projects.is_empty() {
+        vec![DEFAULT_ANDROID_PROJECT.to_owned()]
+    } else {
+        options.android_projects
+    };
+
+    // we copy the selected android targets side by side
+    publish_targets_in_parallel(&android_targets, |android_target| {
+        for android_project in &android_projects {
+            publish_single_target(android_target, Platform::Android, android_project, options.dry_run)?;
+        }
+
+        Ok(())
+    })?;
+
+    if options.dry_run {
+        return Ok("Dry run, nothing was published.".to_owned())
+    }
+
+    let mut manifest_entries = Vec::new();
+    for android_target in &android_targets {
+        manifest_entries.push(manifest_entry(android_target)?);
+    }
+
+    for android_project in &android_projects {
+        write_manifest(android_project, &manifest_entries)?;
     }
 
     Ok("JNI Libs Succesfully Published to the Android Project!!!".to_owned())
 }
 
-fn main() {
-    match publish_jni_lib_to_android_project() {
-        Ok(success_message) => console::print(success_message),
-        Err(error) => console::print(error.to_string()),
+///
+/// Copy the release version of each selected iOS target to the
+/// corresponding framework directory in the iOS project.
+///
+/// ## Arguments
+///
+/// * `options` - The iOS targets given with `--target`, where none means every known target, and whether this is a dry run.
+///
+fn publish_to_ios_project(options: PublishOptions) -> Result<String, Box<dyn Error>> {
+    let ios_targets = if options.targets.is_empty() {
+        build::IOS_TARGET_CONFIG.keys().map(|target| target.to_string()).collect()
+    } else {
+        options.targets
+    };
+
+    for ios_target in ios_targets {
+        publish_single_target(&ios_target, Platform::Ios, DEFAULT_ANDROID_PROJECT, options.dry_run)?;
+    }
+
+    if options.dry_run {
+        return Ok("Dry run, nothing was published.".to_owned())
+    }
+
+    Ok("Static Libs Succesfully Published to the iOS Project!!!".to_owned())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = parse_publish_args(&args)
+        .map_err(Box::<dyn Error>::from)
+        .and_then(|options| match options.platform {
+            Platform::Android => publish_jni_lib_to_android_project(options),
+            Platform::Ios => publish_to_ios_project(options),
+        });
+
+    match result {
+        Ok(success_message) => {
+            console::print(success_message);
+            ExitCode::SUCCESS
+        }
+        Err(error) => {
+            console::print_error(error.to_string());
+            ExitCode::FAILURE
+        }
     }
 }
 
@@ -167,34 +439,169 @@ mod tests {
     use super::*;
 
     #[test]
-    fn android_jni_dir_path_includes_proper_folder_names() {
-        let jni_folder_name = "arm64-v8a";
-        let jni_dir = android_jni_dir_path(jni_folder_name);
+    fn ios_target_config_covers_device_and_simulators() {
+        assert_eq!(build::IOS_TARGET_CONFIG.get("aarch64-apple-ios"), Some(&"ios-arm64"));
+        assert_eq!(build::IOS_TARGET_CONFIG.get("aarch64-apple-ios-sim"), Some(&"ios-arm64-simulator"));
+        assert_eq!(build::IOS_TARGET_CONFIG.get("x86_64-apple-ios"), Some(&"ios-x86_64-simulator"));
+    }
+
+    #[test]
+    fn parse_publish_args_ios() {
+        let options = parse_publish_args(&["--ios".to_owned()]).unwrap();
+
+        assert_eq!(options.platform, Platform::Ios);
+        assert_eq!(parse_publish_args(&[]).unwrap().platform, Platform::Android);
+    }
+
+    #[test]
+    fn publish_single_target_checks_targets_per_platform() {
+        assert!(publish_single_target("aarch64-linux-android", Platform::Ios, DEFAULT_ANDROID_PROJECT, true).is_err());
+        assert!(publish_single_target("aarch64-apple-ios", Platform::Android, DEFAULT_ANDROID_PROJECT, true).is_err());
+    }
+
+    #[test]
+    fn android_targets_to_publish_defaults_to_all_targets() {
+        let android_targets = android_targets_to_publish(&build::default_android_target_abi_config(), None);
+
+        assert_eq!(android_targets.len(), build::ANDROID_TARGET_ABI_CONFIG.len());
+    }
+
+    #[test]
+    fn android_targets_to_publish_intersects_with_known_targets() {
+        let requested_abis = "aarch64-linux-android, not-a-target ,x86_64-linux-android".to_owned();
+        let mut android_targets = android_targets_to_publish(&build::default_android_target_abi_config(), Some(requested_abis));
+        android_targets.sort();
+
+        assert_eq!(android_targets, vec!["aarch64-linux-android", "x86_64-linux-android"]);
+    }
+
+    #[test]
+    fn android_targets_to_publish_without_known_targets_is_empty() {
+        let target_abi_config = build::default_android_target_abi_config();
+
+        assert!(android_targets_to_publish(&target_abi_config, Some("mips-unknown-linux".to_owned())).is_empty());
+        assert!(android_targets_to_publish(&target_abi_config, Some("".to_owned())).is_empty());
+    }
+
+    #[test]
+    fn parse_publish_args_collects_every_target() {
+        let args = ["--target", "aarch64-linux-android", "--target", "x86_64-linux-android"].map(str::to_owned);
+        let options = parse_publish_args(&args).unwrap();
+
+        assert_eq!(options.targets, vec!["aarch64-linux-android", "x86_64-linux-android"]);
+        assert!(!options.dry_run);
+        assert_eq!(parse_publish_args(&[]).unwrap(), PublishOptions::default());
+    }
+
+    #[test]
+    fn parse_publish_args_collects_every_android_project() {
+        let args = ["--android-project", "android-sample", "--android-project", "/apps/wallet-app"].map(str::to_owned);
+        let options = parse_publish_args(&args).unwrap();
+
+        assert_eq!(options.android_projects, vec!["android-sample", "/apps/wallet-app"]);
+        assert!(parse_publish_args(&["--android-project".to_owned()]).is_err());
+        assert!(parse_publish_args(&["--ios", "--android-project", "wallet-app"].map(str::to_owned)).is_err());
+    }
+
+    #[test]
+    fn parse_publish_args_dry_run() {
+        let options = parse_publish_args(&["--dry-run".to_owned()]).unwrap();
+
+        assert!(options.dry_run);
+        assert!(options.targets.is_empty());
+    }
+
+    #[test]
+    fn parse_publish_args_rejects_bad_arguments() {
+        assert!(parse_publish_args(&["--target".to_owned()]).is_err());
+        assert!(parse_publish_args(&["--verbose".to_owned()]).is_err());
+    }
+
+    #[test]
+    fn publish_lib_file_dry_run_never_copies() {
+        // Any file that is sure to exist stands in for a built library.
+        let crate_lib_file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        let android_lib_file_path = env::temp_dir().join(format!("publish-dry-run-{}.so", std::process::id()));
+        let android_lib_file_path = android_lib_file_path.to_str().unwrap();
 
-        assert!(jni_dir.contains("android-sample"));
-        assert!(jni_dir.contains("app"));
-        assert!(jni_dir.contains("src"));
-        assert!(jni_dir.contains("main"));
-        assert!(jni_dir.contains("jniLibs"));
-        assert!(jni_dir.contains(jni_folder_name));
+        assert!(publish_lib_file(crate_lib_file_path, android_lib_file_path, true).is_ok());
+        assert!(!PathBuf::from(android_lib_file_path).exists());
     }
 
     #[test]
-    fn crate_file_path_for_target_includes_proper_folder_names() {
-        let project_dir_path = "fernando";
-        let android_target = "android";
-        let crate_file_path = crate_file_path_for_target(project_dir_path, android_target);
+    fn publish_lib_file_creates_missing_destination_dirs() {
+        let crate_lib_file_path = concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml");
+        let android_project_dir = env::temp_dir().join(format!("publish-fresh-module-{}", std::process::id()));
+        let android_lib_file_path = android_project_dir.join("jniLibs").join("arm64-v8a").join(lib_file_name(Platform::Android));
+
+        assert!(publish_lib_file(crate_lib_file_path, android_lib_file_path.to_str().unwrap(), false).is_ok());
+        assert!(android_lib_file_path.is_file());
+
+        fs::remove_dir_all(android_project_dir).unwrap();
+    }
+
+    #[test]
+    fn manifest_contents_matches_sha256sum_format() {
+        // SHA-256 of "abc", from FIPS 180-2.
+        let entries = [
+            ("ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad".to_owned(), "arm64-v8a/libcryptor_jni.so".to_owned()),
+            ("00".repeat(32), "x86_64/libcryptor_jni.so".to_owned()),
+        ];
+
+        assert_eq!(
+            manifest_contents(&entries),
+            concat!(
+                "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad  arm64-v8a/libcryptor_jni.so\n",
+                "0000000000000000000000000000000000000000000000000000000000000000  x86_64/libcryptor_jni.so\n",
+            )
+        );
+        assert_eq!(manifest_contents(&[]), "");
+    }
+
+    #[test]
+    fn publish_lib_file_dry_run_reports_missing_sources_without_failing() {
+        assert!(publish_lib_file("not/built/yet.so", "never/written.so", true).is_ok());
+        assert!(publish_lib_file("not/built/yet.so", "never/written.so", false).is_err());
+    }
+
+    #[test]
+    fn publish_targets_in_parallel_copies_every_target() {
+        let android_project_dir = env::temp_dir().join(format!("publish-parallel-{}", std::process::id()));
+        let targets = ["armeabi-v7a", "arm64-v8a", "x86", "x86_64"].map(str::to_owned);
+
+        let result = publish_targets_in_parallel(&targets, |target| {
+            let android_lib_file_path = android_project_dir.join(target).join(lib_file_name(Platform::Android));
+            publish_lib_file(concat!(env!("CARGO_MANIFEST_DIR"), "/Cargo.toml"), android_lib_file_path.to_str().unwrap(), false)
+        });
+
+        assert!(result.is_ok());
+        assert!(targets.iter().all(|target| android_project_dir.join(target).join(lib_file_name(Platform::Android)).is_file()));
+
+        fs::remove_dir_all(android_project_dir).unwrap();
+    }
+
+    #[test]
+    fn publish_targets_in_parallel_fails_when_one_target_fails() {
+        let targets = ["aarch64-linux-android", "mips-unknown-linux", "x86_64-linux-android"].map(str::to_owned);
+        let published = std::sync::Mutex::new(Vec::new());
+
+        let error = publish_targets_in_parallel(&targets, |target| {
+            if target == "mips-unknown-linux" {
+                return Err(format!("Unknown android target '{}'", target).into())
+            }
+
+            published.lock().unwrap().push(target.to_owned());
+            Ok(())
+        }).unwrap_err();
 
-        assert!(crate_file_path.contains(project_dir_path));
-        assert!(crate_file_path.contains("target"));
-        assert!(crate_file_path.contains(android_target));
-        assert!(crate_file_path.contains("release"));
+        assert_eq!(error.to_string(), "mips-unknown-linux: Unknown android target 'mips-unknown-linux'");
+        assert_eq!(published.into_inner().unwrap().len(), 2);
     }
 
     #[test]
-    fn proper_project_dir_path() {
-        let project_dir_path = &project_dir_path();
+    fn publish_single_target_rejects_unknown_target() {
+        let error = publish_single_target("mips-unknown-linux", Platform::Android, DEFAULT_ANDROID_PROJECT, false).unwrap_err();
 
-        assert!(project_dir_path.contains("rust-library"));
+        assert!(error.to_string().contains("mips-unknown-linux"));
     }
 }
\ No newline at end of file
@@ -0,0 +1,155 @@
+//!
+//! This is a binary targets, which is an executable program
+//! that can be run after crate compilation.
+//!
+//! It will basically check that the library of each target
+//! published to the android project is the one last built,
+//! by comparing their SHA-256.
+//!
+//! ## Examples
+//! ```
+//! $ cd cryptor_jni/
+//! $ cargo run --bin verify
+//! ```
+//!
+//! Exits with a non-zero status if any published library differs from
+//! its build, or either of them is missing.
+//!
+//! For more information, refer to the official doc:
+//!  - https://doc.rust-lang.org/cargo/reference/cargo-targets.html#binaries
+//!
+
+
+// https://doc.rust-lang.org/reference/items/modules.html
+#[path="../../build.rs"]
+mod build;
+#[path="../paths.rs"]
+mod paths;
+
+use std::process::ExitCode;
+
+use ancryptor_global::{
+    console,
+    io,
+};
+
+use paths::{
+    DEFAULT_ANDROID_PROJECT,
+    Platform,
+    project_dir_path,
+    android_jni_dir_path,
+    crate_file_path_for_target,
+};
+
+///
+/// Checks that the published library has the same SHA-256 as
+/// the built one.
+///
+/// ## Arguments
+///
+/// * `crate_lib_file_path` - A string slice that holds the path of the built library.
+/// * `android_lib_file_path` - A string slice that holds its published copy in the android project.
+///
+fn verify_lib_file(crate_lib_file_path: &str, android_lib_file_path: &str) -> Result<(), String> {
+    let crate_lib_digest = io::sha256_file(crate_lib_file_path)
+        .map_err(|error| format!("Cannot read {}: {}", crate_lib_file_path, error))?;
+    let android_lib_digest = io::sha256_file(android_lib_file_path)
+        .map_err(|error| format!("Cannot read {}: {}", android_lib_file_path, error))?;
+
+    if crate_lib_digest != android_lib_digest {
+        return Err(format!("{} does not match {}", android_lib_file_path, crate_lib_file_path));
+    }
+
+    Ok(())
+}
+
+///
+/// Verifies the library published for each configured android
+/// target and returns how many of them failed the check.
+///
+fn verify_android_project() -> Result<usize, String> {
+    let mut failures = 0;
+
+    for (android_target, target_abi) in build::android_target_abi_config()? {
+        let crate_lib_file_path = crate_file_path_for_target(&project_dir_path(), &android_target, Platform::Android);
+        let android_lib_file_path = android_jni_dir_path(DEFAULT_ANDROID_PROJECT, &target_abi.abi);
+
+        match verify_lib_file(&crate_lib_file_path, &android_lib_file_path) {
+            Ok(()) => console::print_info(format!("{} --> up to date", android_target)),
+            Err(error) => {
+                console::print_error(format!("{} --> {}", android_target, error));
+                failures += 1;
+            }
+        }
+    }
+
+    Ok(failures)
+}
+
+fn main() -> ExitCode {
+    match verify_android_project() {
+        Ok(0) => {
+            console::print_info("JNI Libs in the Android Project match the build".to_owned());
+            ExitCode::SUCCESS
+        }
+        Ok(failures) => {
+            console::print_error(format!("{} JNI Lib(s) in the Android Project do not match the build", failures));
+            ExitCode::FAILURE
+        }
+        Err(error) => {
+            console::print_error(error);
+            ExitCode::FAILURE
+        }
+    }
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::env;
+    use std::fs;
+
+    fn temp_lib_file(name: &str, contents: &str) -> String {
+        let lib_file_path = env::temp_dir().join(format!("verify-{}-{}.so", name, std::process::id()));
+        fs::write(&lib_file_path, contents).unwrap();
+
+        lib_file_path.to_str().unwrap().to_owned()
+    }
+
+    #[test]
+    fn verify_lib_file_accepts_identical_libs() {
+        let crate_lib_file_path = temp_lib_file("match-built", "release build");
+        let android_lib_file_path = temp_lib_file("match-published", "release build");
+
+        assert_eq!(verify_lib_file(&crate_lib_file_path, &android_lib_file_path), Ok(()));
+
+        fs::remove_file(crate_lib_file_path).unwrap();
+        fs::remove_file(android_lib_file_path).unwrap();
+    }
+
+    #[test]
+    fn verify_lib_file_rejects_stale_libs() {
+        let crate_lib_file_path = temp_lib_file("mismatch-built", "release build");
+        let android_lib_file_path = temp_lib_file("mismatch-published", "older build");
+
+        let error = verify_lib_file(&crate_lib_file_path, &android_lib_file_path).unwrap_err();
+
+        assert!(error.contains("does not match"));
+        fs::remove_file(crate_lib_file_path).unwrap();
+        fs::remove_file(android_lib_file_path).unwrap();
+    }
+
+    #[test]
+    fn verify_lib_file_rejects_missing_published_lib() {
+        let crate_lib_file_path = temp_lib_file("missing-built", "release build");
+        let android_lib_file_path = env::temp_dir().join(format!("verify-never-published-{}.so", std::process::id()));
+        let android_lib_file_path = android_lib_file_path.to_str().unwrap();
+
+        let error = verify_lib_file(&crate_lib_file_path, android_lib_file_path).unwrap_err();
+
+        assert!(error.starts_with(&format!("Cannot read {}", android_lib_file_path)));
+        fs::remove_file(crate_lib_file_path).unwrap();
+    }
+}
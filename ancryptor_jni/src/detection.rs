@@ -0,0 +1,37 @@
+use ancryptor::EncodingKind;
+
+// Returned when the input decodes as none of the supported encodings.
+static UNKNOWN_ENCODING: &str = "unknown";
+
+///
+/// Returns the name reported to Kotlin callers for a detected encoding,
+/// or `"unknown"` when nothing was detected.
+///
+pub fn encoding_name_for(kind: Option<EncodingKind>) -> &'static str {
+    match kind {
+        Some(EncodingKind::Base64) => "base64",
+        Some(EncodingKind::Base64UrlSafe) => "base64url",
+        Some(EncodingKind::Base32) => "base32",
+        Some(EncodingKind::Hex) => "hex",
+        None => UNKNOWN_ENCODING,
+    }
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_detected_kinds_have_names() {
+        assert_eq!(encoding_name_for(ancryptor::detect_encoding("aGk=")), "base64");
+        assert_eq!(encoding_name_for(ancryptor::detect_encoding("6869")), "hex");
+    }
+
+    #[test]
+    fn test_undetected_input_is_unknown() {
+        assert_eq!(encoding_name_for(ancryptor::detect_encoding("not encoded!")), "unknown");
+    }
+}
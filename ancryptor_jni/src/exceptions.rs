@@ -0,0 +1,46 @@
+use ancryptor::DecodeError;
+
+// JNI class names use `/` instead of `.` as the package separator.
+pub static ILLEGAL_ARGUMENT_EXCEPTION: &str = "java/lang/IllegalArgumentException";
+// `java.nio.charset.CharacterCodingException` has no message constructor,
+// so the Kotlin side declares a subclass of it that takes one.
+static CHARACTER_CODING_EXCEPTION: &str = "com/abhaynaik/rust/CharacterCodingException";
+
+///
+/// Returns the Java exception class thrown for `error`, so Kotlin callers
+/// can tell malformed input from a payload that is not valid text.
+///
+pub fn exception_class_for(error: &DecodeError) -> &'static str {
+    match error {
+        DecodeError::InvalidBase64(_)
+        | DecodeError::InvalidBase32(_)
+        | DecodeError::InvalidHex(_)
+        | DecodeError::InvalidGzip(_)
+        | DecodeError::InvalidBase58(_) => {
+            ILLEGAL_ARGUMENT_EXCEPTION
+        }
+        DecodeError::InvalidUtf8(_) => CHARACTER_CODING_EXCEPTION,
+    }
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_invalid_base64_maps_to_illegal_argument_exception() {
+        let error = ancryptor::try_decode("dfoiuerw892").unwrap_err();
+
+        assert_eq!(exception_class_for(&error), "java/lang/IllegalArgumentException");
+    }
+
+    #[test]
+    fn test_invalid_utf8_maps_to_character_coding_exception() {
+        let error = ancryptor::try_decode("aGn/").unwrap_err();
+
+        assert_eq!(exception_class_for(&error), "com/abhaynaik/rust/CharacterCodingException");
+    }
+}
@@ -1,3 +1,6 @@
+pub mod detection;
+pub mod exceptions;
+
 //
 /// [cfg(target_os = "android")]: Compiler flag ("cfg") which exposes
 /// the JNI interface for targeting Android in this case
@@ -22,15 +25,42 @@ pub mod android {
     // native function. They carry extra lifetime information to
     // prevent them escaping this context and getting used after
     // being GC'd.
-    use self::jni::objects::{JClass, JString};
+    use self::jni::objects::{JByteArray, JClass, JString};
 
     // This is just a pointer. We'll be returning it from our function.
     // We can't return one of the objects with lifetime information
     // because the lifetime checker won't let us.
-    use self::jni::sys::jstring;
+    use self::jni::sys::{jbyteArray, jobject, jstring};
 
     use ancryptor::encode;
     use ancryptor::decode;
+    use ancryptor::try_decode;
+    use ancryptor::detect_encoding;
+    use ancryptor::encode_bytes;
+    use ancryptor::decode_bytes;
+    use ancryptor::version;
+
+    use crate::detection::encoding_name_for;
+    use crate::exceptions::{exception_class_for, ILLEGAL_ARGUMENT_EXCEPTION};
+
+    ///
+    /// Throws `exception_class` with `message` and returns the null object
+    /// that JNI functions hand back while an exception is pending, so a
+    /// failure reaches Kotlin/Java as an exception instead of a panic that
+    /// would abort the app.
+    ///
+    /// If the JVM already has an exception pending, e.g. an
+    /// `OutOfMemoryError` from a failed allocation, that one is kept.
+    ///
+    fn throw_and_return(env: &mut JNIEnv, exception_class: &str, message: &str) -> jobject {
+        if !env.exception_check().unwrap_or(true) {
+            // Nothing more can be done if even throwing fails.
+            let _ = env.throw_new(exception_class, message);
+        }
+
+        // The return value is ignored once an exception is pending.
+        std::ptr::null_mut()
+    }
 
     ///
     /// Encodes a String.
@@ -45,23 +75,29 @@ pub mod android {
         input: JString<'local>,
     ) -> jstring {
 
-        native_activity_create();
-
         // First, we have to get the string out of Java. Check out the `strings`
         // module for more info on how this works.
-        let to_encode: String = env.get_string(&input)
-                                    .expect("Couldn't get java string!").into();
+        let to_encode: String = match env.get_string(&input) {
+            Ok(to_encode) => to_encode.into(),
+            Err(error) => {
+                let message = format!("Couldn't get java string: {}", error);
+                return throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message);
+            }
+        };
 
         // We encode our str calling the cryptor library
         let encoded_str = encode(&to_encode);
 
         // Here we have to create a new Java string to return. Again, more info
         // in the `strings` module.
-        let output = env.new_string(&encoded_str)
-                        .expect("Couldn't create Java String!");
-
-        // Finally, extract the raw pointer to return.
-        output.into_raw()
+        match env.new_string(&encoded_str) {
+            // Finally, extract the raw pointer to return.
+            Ok(output) => output.into_raw(),
+            Err(error) => {
+                let message = format!("Couldn't create Java String: {}", error);
+                throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message)
+            }
+        }
     }
 
     ///
@@ -79,18 +115,186 @@ pub mod android {
 
         // First, we have to get the string out of Java. Check out the `strings`
         // module for more info on how this works.
-        let to_decode: String = env.get_string(&input).expect("Couldn't get java string!").into();
+        let to_decode: String = match env.get_string(&input) {
+            Ok(to_decode) => to_decode.into(),
+            Err(error) => {
+                let message = format!("Couldn't get java string: {}", error);
+                return throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message);
+            }
+        };
 
         // We decode our str calling the cryptor library
-        let decoded_str = decode(&to_decode.to_owned());
+        let decoded_str = decode(&to_decode);
 
         // Here we have to create a new Java string to return. Again, more info
         // in the `strings` module.
-        let output = env.new_string(&decoded_str).expect("Couldn't create Java String!");
+        match env.new_string(&decoded_str) {
+            // Finally, extract the raw pointer to return.
+            Ok(output) => output.into_raw(),
+            Err(error) => {
+                let message = format!("Couldn't create Java String: {}", error);
+                throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message)
+            }
+        }
+    }
 
+    ///
+    /// Decodes a String, throwing a Java exception when it cannot be decoded:
+    /// `IllegalArgumentException` for malformed base64 and
+    /// `com.abhaynaik.rust.CharacterCodingException` for invalid UTF-8.
+    ///
+    #[no_mangle] // This keeps Rust from "mangling" the name so it is unique (crate).
+    pub extern "system" fn Java_com_abhaynaik_rust_Cryptor_tryDecode<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        input: JString<'local>,
+    ) -> jstring {
+
+        let to_decode: String = match env.get_string(&input) {
+            Ok(to_decode) => to_decode.into(),
+            Err(error) => {
+                let message = format!("Couldn't get java string: {}", error);
+                return throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message);
+            }
+        };
+
+        let decoded_str = match try_decode(&to_decode) {
+            Ok(decoded_str) => decoded_str,
+            Err(error) => return throw_and_return(&mut env, exception_class_for(&error), &error.to_string()),
+        };
+
+        match env.new_string(&decoded_str) {
+            Ok(output) => output.into_raw(),
+            Err(error) => {
+                let message = format!("Couldn't create Java String: {}", error);
+                throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message)
+            }
+        }
+    }
+
+    ///
+    /// Reports which encoding a String most likely is: `"base64"`,
+    /// `"base64url"`, `"base32"`, `"hex"` or `"unknown"`. A null String
+    /// is `"unknown"`.
+    ///
+    #[no_mangle] // This keeps Rust from "mangling" the name so it is unique (crate).
+    pub extern "system" fn Java_com_abhaynaik_rust_Cryptor_detectEncoding<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        input: JString<'local>,
+    ) -> jstring {
+
+        let kind = if input.is_null() {
+            None
+        } else {
+            match env.get_string(&input) {
+                Ok(to_detect) => detect_encoding(&String::from(to_detect)),
+                Err(error) => {
+                    let message = format!("Couldn't get java string: {}", error);
+                    return throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message);
+                }
+            }
+        };
+
+        match env.new_string(encoding_name_for(kind)) {
+            Ok(output) => output.into_raw(),
+            Err(error) => {
+                let message = format!("Couldn't create Java String: {}", error);
+                throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message)
+            }
+        }
+    }
+
+    ///
+    /// Encodes a byte array, e.g. the contents of a file, without going
+    /// through a Java String first.
+    ///
+    /// Declared on the Java side as:
+    ///
+    /// ```java
+    /// public static native String encodeBytes(byte[] input);
+    /// ```
+    ///
+    #[no_mangle] // This keeps Rust from "mangling" the name so it is unique (crate).
+    pub extern "system" fn Java_com_abhaynaik_rust_Cryptor_encodeBytes<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        input: JByteArray<'local>,
+    ) -> jstring {
+
+        let to_encode: Vec<u8> = match env.convert_byte_array(&input) {
+            Ok(to_encode) => to_encode,
+            Err(error) => {
+                let message = format!("Couldn't get java byte array: {}", error);
+                return throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message);
+            }
+        };
+
+        match env.new_string(encode_bytes(&to_encode)) {
+            Ok(output) => output.into_raw(),
+            Err(error) => {
+                let message = format!("Couldn't create Java String: {}", error);
+                throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message)
+            }
+        }
+    }
+
+    ///
+    /// Decodes a String into the raw bytes it holds, binary data included.
+    /// Throws the same exceptions as `tryDecode` for malformed input.
+    ///
+    /// Declared on the Java side as:
+    ///
+    /// ```java
+    /// public static native byte[] decodeBytes(String input);
+    /// ```
+    ///
+    #[no_mangle] // This keeps Rust from "mangling" the name so it is unique (crate).
+    pub extern "system" fn Java_com_abhaynaik_rust_Cryptor_decodeBytes<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+        input: JString<'local>,
+    ) -> jbyteArray {
+
+        let to_decode: String = match env.get_string(&input) {
+            Ok(to_decode) => to_decode.into(),
+            Err(error) => {
+                let message = format!("Couldn't get java string: {}", error);
+                return throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message);
+            }
+        };
+
+        let decoded = match decode_bytes(&to_decode) {
+            Ok(decoded) => decoded,
+            Err(error) => return throw_and_return(&mut env, exception_class_for(&error), &error.to_string()),
+        };
+
+        match env.byte_array_from_slice(&decoded) {
+            Ok(output) => output.into_raw(),
+            Err(error) => {
+                let message = format!("Couldn't create Java byte array: {}", error);
+                throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message)
+            }
+        }
+    }
+
+    ///
+    /// Returns the version of the `ancryptor` library this native library
+    /// was built from, e.g. `"0.1.0"`, to check which `.so` was loaded.
+    ///
+    #[no_mangle] // This keeps Rust from "mangling" the name so it is unique (crate).
+    pub extern "system" fn Java_com_abhaynaik_rust_Cryptor_nativeVersion<'local>(
+        mut env: JNIEnv<'local>,
+        _class: JClass<'local>,
+    ) -> jstring {
 
-        // Finally, extract the raw pointer to return.
-        output.into_raw()
+        match env.new_string(version()) {
+            Ok(output) => output.into_raw(),
+            Err(error) => {
+                let message = format!("Couldn't create Java String: {}", error);
+                throw_and_return(&mut env, ILLEGAL_ARGUMENT_EXCEPTION, &message)
+            }
+        }
     }
 
-}
\ No newline at end of file
+}
@@ -0,0 +1,325 @@
+//!
+//! The paths the binaries of this crate read libraries from and
+//! publish them to, shared by `publish` and `clean`.
+//!
+
+// Avoid false positive warning due to
+// calling members from another module.
+#![allow(dead_code)]
+
+use std::env;
+use std::path::PathBuf;
+use std::path::MAIN_SEPARATOR_STR;
+
+// The android project published to when none is given, next to this repository
+pub static DEFAULT_ANDROID_PROJECT: &str = "android-sample";
+
+// Represent the lib file names the apps load on each platform
+pub static JNI_LIB_FILE_NAME: &str = "libcryptor_jni.so";
+pub static IOS_LIB_FILE_NAME: &str = "libcryptor_jni.a";
+
+///
+/// The platforms the library can be published to.
+///
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Platform {
+    // A shared library loaded through JNI.
+    #[default]
+    Android,
+    // A static library linked into the app.
+    Ios,
+}
+
+///
+/// Returns the name the library is published under for `platform`,
+/// which is the name the app loads it by.
+///
+/// ## Arguments
+///
+/// * `platform` - The platform the library is published to.
+///
+pub fn lib_file_name(platform: Platform) -> &'static str {
+    match platform {
+        Platform::Android => JNI_LIB_FILE_NAME,
+        Platform::Ios => IOS_LIB_FILE_NAME,
+    }
+}
+
+///
+/// Returns the name of the shared library cargo builds from this crate:
+/// `lib<package name>.so`, with any `-` of the package name replaced
+/// by `_` like cargo does, so renaming the crate does not break publish.
+///
+/// Falls back to `JNI_LIB_FILE_NAME` when not built by cargo.
+///
+/// ## Examples
+///
+/// `$ libancryptor_jni.so`
+///
+pub fn jni_lib_file_name() -> String {
+    crate_lib_file_name("so").unwrap_or_else(|| JNI_LIB_FILE_NAME.to_owned())
+}
+
+///
+/// Returns the name of the library file cargo builds for `platform`.
+///
+/// ## Arguments
+///
+/// * `platform` - The platform the library is built for.
+///
+fn built_lib_file_name(platform: Platform) -> String {
+    match platform {
+        Platform::Android => jni_lib_file_name(),
+        Platform::Ios => crate_lib_file_name("a").unwrap_or_else(|| IOS_LIB_FILE_NAME.to_owned()),
+    }
+}
+
+fn crate_lib_file_name(extension: &str) -> Option<String> {
+    option_env!("CARGO_PKG_NAME").map(|package_name| format!("lib{}.{}", package_name.replace('-', "_"), extension))
+}
+
+///
+/// Returns the project directory path.
+///
+/// ## Examples
+///
+/// `$ rust-library/`
+///
+pub fn project_dir_path() -> String {
+    let current_dir_path = env::current_dir().expect(
+        "Cannot read current directory"
+    );
+    let target_dir_path = current_dir_path.parent().expect(
+        "Cannot find/read 'rust-library' directory"
+    );
+
+    target_dir_path.as_os_str().to_str().expect(
+        "Cannot validate 'rust-library' directory"
+    ).to_owned()
+}
+
+///
+/// Returns the jniLibs directory path in the android project,
+/// which holds a folder per android target.
+///
+/// ## Arguments
+///
+/// * `android_project` - A string slice that holds the android project directory, either absolute or relative to the directory holding this repository.
+///
+/// ## Examples
+///
+/// `$ android-sample/app/src/main/jniLibs`
+///
+pub fn android_jni_libs_dir_path(android_project: &str) -> String {
+    let mut android_jni_file_path = if PathBuf::from(android_project).is_absolute() {
+        android_project.to_owned()
+    } else {
+        let project_dir = PathBuf::from(project_dir_path());
+        let android_project_dir_path = project_dir.parent().expect(
+            "Cannot find/read the android project directory"
+        );
+
+        let mut android_project_path = android_project_dir_path.as_os_str().to_str().expect(
+            "Cannot validate the android project directory"
+        ).to_owned();
+
+        android_project_path.push_str(MAIN_SEPARATOR_STR);
+        android_project_path.push_str(android_project);
+        android_project_path
+    };
+
+    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
+    android_jni_file_path.push_str("app");
+    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
+    android_jni_file_path.push_str("src");
+    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
+    android_jni_file_path.push_str("main");
+    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
+    android_jni_file_path.push_str("jniLibs");
+
+    android_jni_file_path
+}
+
+///
+/// Returns the jni directory path in the android project
+/// where the release version of this crate should be
+/// placed.
+///
+/// ## Arguments
+///
+/// * `android_project` - A string slice that holds the android project directory, see [`android_jni_libs_dir_path`].
+/// * `android_jni_lib_folder` - A string slice that holds the name of the android target.
+///
+/// ## Examples
+///
+/// `$ android-sample/app/src/main/jniLibs/arm64-v8a/JNI_LIB_FILE_NAME`
+///
+pub fn android_jni_dir_path(android_project: &str, android_jni_lib_folder: &str) -> String {
+    let mut android_jni_file_path = android_jni_libs_dir_path(android_project);
+
+    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
+    android_jni_file_path.push_str(android_jni_lib_folder);
+    android_jni_file_path.push_str(MAIN_SEPARATOR_STR);
+    android_jni_file_path.push_str(lib_file_name(Platform::Android));
+
+    android_jni_file_path
+}
+
+///
+/// Returns the path in the iOS project where the release
+/// version of this crate should be placed.
+///
+/// ## Arguments
+///
+/// * `ios_framework_folder` - A string slice that holds the name of the iOS target slice.
+///
+/// ## Examples
+///
+/// `$ ios-sample/Frameworks/ios-arm64/IOS_LIB_FILE_NAME`
+///
+pub fn ios_framework_dir_path(ios_framework_folder: &str) -> String {
+    let project_dir = PathBuf::from(project_dir_path());
+    let ios_project_dir_path = project_dir.parent().expect(
+        "Cannot find/read 'ios-sample' directory"
+    );
+
+    let mut ios_lib_file_path = ios_project_dir_path.as_os_str().to_str().expect(
+        "Cannot validate 'ios-sample' directory"
+    ).to_owned();
+
+    ios_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    ios_lib_file_path.push_str("ios-sample");
+    ios_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    ios_lib_file_path.push_str("Frameworks");
+    ios_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    ios_lib_file_path.push_str(ios_framework_folder);
+    ios_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    ios_lib_file_path.push_str(lib_file_name(Platform::Ios));
+
+    ios_lib_file_path
+}
+
+///
+/// Returns the file path where the
+/// release version of this crate
+/// is placed.
+///
+/// ## Arguments
+///
+/// * `project_dir_path` - A string slice that holds thsi project directory path.
+/// * `target` - A string slice that holds the name of the android or iOS target.
+/// * `platform` - The platform of `target`, which decides the library file name.
+///
+/// ## Examples
+///
+/// `$ rust-library/target/x86_64-linux-android/release/libancryptor_jni.so`
+///
+pub fn crate_file_path_for_target(project_dir_path: &str, target: &str, platform: Platform) -> String {
+    let mut crate_lib_file_path = project_dir_path.to_owned();
+
+    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    crate_lib_file_path.push_str("target");
+    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    crate_lib_file_path.push_str(target);
+    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    crate_lib_file_path.push_str("release");
+    crate_lib_file_path.push_str(MAIN_SEPARATOR_STR);
+    crate_lib_file_path.push_str(&built_lib_file_name(platform));
+
+    crate_lib_file_path
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn android_jni_dir_path_includes_proper_folder_names() {
+        let jni_folder_name = "arm64-v8a";
+        let jni_dir = android_jni_dir_path(DEFAULT_ANDROID_PROJECT, jni_folder_name);
+
+        assert!(jni_dir.contains("android-sample"));
+        assert!(jni_dir.contains("app"));
+        assert!(jni_dir.contains("src"));
+        assert!(jni_dir.contains("main"));
+        assert!(jni_dir.contains("jniLibs"));
+        assert!(jni_dir.contains(jni_folder_name));
+    }
+
+    #[test]
+    fn ios_framework_dir_path_includes_proper_folder_names() {
+        let ios_framework_folder = "ios-arm64";
+        let framework_dir = ios_framework_dir_path(ios_framework_folder);
+
+        assert!(framework_dir.contains("ios-sample"));
+        assert!(framework_dir.contains("Frameworks"));
+        assert!(framework_dir.contains(ios_framework_folder));
+        assert!(framework_dir.ends_with("libcryptor_jni.a"));
+    }
+
+    #[test]
+    fn lib_file_name_depends_on_platform() {
+        assert_eq!(lib_file_name(Platform::Android), "libcryptor_jni.so");
+        assert_eq!(lib_file_name(Platform::Ios), "libcryptor_jni.a");
+        assert!(android_jni_dir_path(DEFAULT_ANDROID_PROJECT, "arm64-v8a").ends_with("libcryptor_jni.so"));
+        assert!(crate_file_path_for_target("fernando", "aarch64-apple-ios", Platform::Ios).ends_with(".a"));
+    }
+
+    #[test]
+    fn jni_lib_file_name_follows_package_name() {
+        let jni_lib_file_name = jni_lib_file_name();
+
+        assert!(jni_lib_file_name.starts_with("lib"));
+        assert!(jni_lib_file_name.ends_with(".so"));
+        assert_eq!(jni_lib_file_name, format!("lib{}.so", env!("CARGO_PKG_NAME")));
+        assert!(crate_file_path_for_target("fernando", "x86_64-linux-android", Platform::Android).ends_with(&jni_lib_file_name));
+    }
+
+    #[test]
+    fn crate_file_path_for_target_includes_proper_folder_names() {
+        let project_dir_path = "fernando";
+        let android_target = "android";
+        let crate_file_path = crate_file_path_for_target(project_dir_path, android_target, Platform::Android);
+
+        assert!(crate_file_path.contains(project_dir_path));
+        assert!(crate_file_path.contains("target"));
+        assert!(crate_file_path.contains(android_target));
+        assert!(crate_file_path.contains("release"));
+    }
+
+    #[test]
+    fn android_jni_dir_path_is_inside_jni_libs_dir() {
+        assert!(android_jni_dir_path(DEFAULT_ANDROID_PROJECT, "x86").starts_with(&android_jni_libs_dir_path(DEFAULT_ANDROID_PROJECT)));
+        assert!(android_jni_libs_dir_path(DEFAULT_ANDROID_PROJECT).ends_with("jniLibs"));
+    }
+
+    #[test]
+    fn android_jni_dir_path_depends_on_android_project() {
+        let sample_jni_dir = android_jni_dir_path("android-sample", "arm64-v8a");
+        let wallet_jni_dir = android_jni_dir_path("wallet-app", "arm64-v8a");
+
+        assert!(sample_jni_dir.contains("android-sample"));
+        assert!(!sample_jni_dir.contains("wallet-app"));
+        assert!(wallet_jni_dir.contains("wallet-app"));
+        assert!(!wallet_jni_dir.contains("android-sample"));
+        assert_eq!(sample_jni_dir.replace("android-sample", "wallet-app"), wallet_jni_dir);
+    }
+
+    #[test]
+    fn android_jni_libs_dir_path_keeps_absolute_android_project() {
+        let android_project = env::temp_dir().join("apps").join("wallet-app");
+        let jni_libs_dir = android_jni_libs_dir_path(android_project.to_str().unwrap());
+
+        assert_eq!(PathBuf::from(jni_libs_dir), android_project.join("app").join("src").join("main").join("jniLibs"));
+    }
+
+    #[test]
+    fn proper_project_dir_path() {
+        let project_dir_path = &project_dir_path();
+
+        assert!(project_dir_path.contains("rust-library"));
+    }
+}
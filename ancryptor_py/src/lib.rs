@@ -0,0 +1,125 @@
+//!
+//! Python bindings for the `ancryptor` crate, built on PyO3, the
+//! Python counterpart of `ancryptor_jni`.
+//!
+//! ## Examples
+//! ```sh
+//! $ cd ancryptor_py/
+//! $ pip install maturin && maturin develop
+//! ```
+//!
+//! ```python
+//! import ancryptor_py
+//!
+//! ancryptor_py.decode(ancryptor_py.encode("hello"))   # 'hello'
+//! ancryptor_py.decode_bytes("aGn/")                    # b'hi\xff'
+//! ancryptor_py.decode("not base64!")                   # raises ValueError
+//! ```
+//!
+
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use pyo3::types::PyBytes;
+
+///
+/// Encodes a str as standard, padded base64.
+///
+#[pyfunction]
+fn encode(input: &str) -> String {
+    ancryptor::encode(input)
+}
+
+///
+/// Decodes standard, padded base64 into a str, raising `ValueError` when
+/// the input is not valid base64 or not valid UTF-8.
+///
+#[pyfunction]
+fn decode(input: &str) -> PyResult<String> {
+    ancryptor::try_decode(input).map_err(|error| PyValueError::new_err(error.to_string()))
+}
+
+///
+/// Encodes bytes, e.g. the contents of a file, as standard, padded base64.
+///
+#[pyfunction]
+fn encode_bytes(input: &[u8]) -> String {
+    ancryptor::encode_bytes(input)
+}
+
+///
+/// Decodes standard, padded base64 into bytes, binary data included,
+/// raising `ValueError` when the input is not valid base64.
+///
+#[pyfunction]
+fn decode_bytes<'py>(py: Python<'py>, input: &str) -> PyResult<Bound<'py, PyBytes>> {
+    let decoded = ancryptor::decode_bytes(input).map_err(|error| PyValueError::new_err(error.to_string()))?;
+
+    Ok(PyBytes::new(py, &decoded))
+}
+
+///
+/// Registers the functions of the `ancryptor_py` Python module.
+///
+#[pymodule]
+fn ancryptor_py(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_function(wrap_pyfunction!(encode, module)?)?;
+    module.add_function(wrap_pyfunction!(decode, module)?)?;
+    module.add_function(wrap_pyfunction!(encode_bytes, module)?)?;
+    module.add_function(wrap_pyfunction!(decode_bytes, module)?)?;
+
+    Ok(())
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_encode_then_decode_round_trips() {
+        let encoded = encode("hello_world_from_rust");
+
+        assert_eq!(encoded, "aGVsbG9fd29ybGRfZnJvbV9ydXN0");
+        assert_eq!(decode(&encoded).unwrap(), "hello_world_from_rust");
+    }
+
+    #[test]
+    fn test_decode_failures_raise_value_error() {
+        Python::with_gil(|py| {
+            for invalid in ["dfoiuerw892", "aGn/"] {
+                let error = decode(invalid).unwrap_err();
+
+                assert!(error.is_instance_of::<PyValueError>(py), "{}", invalid);
+            }
+        });
+    }
+
+    #[test]
+    fn test_bytes_round_trip_keeps_binary_data() {
+        Python::with_gil(|py| {
+            let encoded = encode_bytes(&[0x68, 0x69, 0xFF]);
+            let decoded = decode_bytes(py, &encoded).unwrap();
+
+            assert_eq!(encoded, "aGn/");
+            assert_eq!(decoded.as_bytes(), [0x68, 0x69, 0xFF]);
+            assert!(decode_bytes(py, "dfoiuerw892").unwrap_err().is_instance_of::<PyValueError>(py));
+        });
+    }
+
+    #[test]
+    fn test_module_registers_every_function() {
+        Python::with_gil(|py| {
+            let module = PyModule::new(py, "ancryptor_py").unwrap();
+            ancryptor_py(&module).unwrap();
+
+            for name in ["encode", "decode", "encode_bytes", "decode_bytes"] {
+                assert!(module.hasattr(name).unwrap(), "{}", name);
+            }
+
+            let decoded: String = module.getattr("decode").unwrap().call1(("aGk=",)).unwrap().extract().unwrap();
+            assert_eq!(decoded, "hi");
+        });
+    }
+}
@@ -0,0 +1,61 @@
+//!
+//! WebAssembly bindings for the `ancryptor` crate, the browser
+//! counterpart of `ancryptor_jni`.
+//!
+//! ## Examples
+//! ```sh
+//! $ cd ancryptor_wasm/
+//! $ wasm-pack build --target web
+//! $ cargo test --target wasm32-unknown-unknown
+//! ```
+//!
+//! ```js
+//! import init, { encode, decode } from "./pkg/ancryptor_wasm.js";
+//!
+//! await init();
+//! decode(encode("hello")); // "hello"
+//! decode("not base64!");   // throws Error("invalid base64: ...")
+//! ```
+//!
+
+use wasm_bindgen::prelude::*;
+
+///
+/// Encodes a String as standard, padded base64.
+///
+#[wasm_bindgen]
+pub fn encode(input: &str) -> String {
+    ancryptor::encode(input)
+}
+
+///
+/// Decodes standard, padded base64 into a String, throwing a JS `Error`
+/// when the input is not valid base64 or not valid UTF-8.
+///
+#[wasm_bindgen]
+pub fn decode(input: &str) -> Result<String, JsValue> {
+    ancryptor::try_decode(input).map_err(|error| JsError::new(&error.to_string()).into())
+}
+
+//
+// T E S T S
+//
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use wasm_bindgen_test::*;
+
+    #[wasm_bindgen_test]
+    fn test_encode_then_decode_round_trips() {
+        let encoded = encode("hello_world_from_rust");
+
+        assert_eq!(encoded, "aGVsbG9fd29ybGRfZnJvbV9ydXN0");
+        assert_eq!(decode(&encoded).unwrap(), "hello_world_from_rust");
+    }
+
+    #[wasm_bindgen_test]
+    fn test_decode_invalid_base64_throws() {
+        assert!(decode("dfoiuerw892").is_err());
+        assert!(decode("aGn/").is_err());
+    }
+}